@@ -0,0 +1,127 @@
+//! Generates the regression tests in `tests/regression.rs`: one test per day,
+//! variant, input file and part that has an answer in the file's `.expected`
+//! sidecar. The variants come from the table in `src/registry/variants.rs`,
+//! which is included here as the build script cannot call into the crate it
+//! builds.
+//! The tests on the real puzzle inputs are ignored unless asked for, as some
+//! of them take minutes.
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+include!("src/registry/variants.rs");
+
+const INPUT_ROOT: &str = "input";
+const VARIANT_TABLE: &str = "src/registry/variants.rs";
+/// Name of the real puzzle input in each day's directory.
+const PUZZLE_INPUT: &str = "input";
+
+fn main() {
+    println!("cargo:rerun-if-changed={INPUT_ROOT}");
+    println!("cargo:rerun-if-changed={VARIANT_TABLE}");
+
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let out_path = PathBuf::from(env::var("OUT_DIR").unwrap()).join("regression_tests.rs");
+    let mut code = String::new();
+
+    for (day, directory) in day_directories(&manifest_dir.join(INPUT_ROOT)) {
+        let variants = variants(day);
+        let tests: Vec<_> = inputs(&directory)
+            .into_iter()
+            .flat_map(|input| {
+                let parts = expected_parts(&input.with_extension("expected"));
+                parts
+                    .into_iter()
+                    .flat_map(|part| variants.iter().map(move |variant| (variant, part)))
+                    .map(move |(variant, part)| test(day, &input, variant, part))
+            })
+            .collect();
+
+        if !tests.is_empty() {
+            code.push_str(&format!("mod day{day} {{\n{}}}\n\n", tests.concat()));
+        }
+    }
+
+    fs::write(out_path, code).unwrap();
+}
+
+fn day_directories(root: &Path) -> Vec<(u8, PathBuf)> {
+    let Ok(entries) = fs::read_dir(root) else {
+        return vec![];
+    };
+    let mut directories: Vec<_> = entries
+        .flatten()
+        .filter(|x| x.file_type().is_ok_and(|t| t.is_dir()))
+        .filter_map(|x| {
+            let day = x.file_name().to_str()?.strip_prefix("day")?.parse().ok()?;
+            Some((day, x.path()))
+        })
+        .collect();
+    directories.sort();
+    directories
+}
+
+/// The variants of `day` in the table, which must list at least the default
+/// one for every day that has an input directory.
+fn variants(day: u8) -> &'static [&'static str] {
+    match VARIANTS.iter().find(|(x, _)| *x == day) {
+        Some((_, variants)) if !variants.is_empty() => variants,
+        _ => panic!("day {day} has an input directory but no variants in {VARIANT_TABLE}"),
+    }
+}
+
+fn inputs(directory: &Path) -> Vec<PathBuf> {
+    let mut inputs: Vec<_> = fs::read_dir(directory)
+        .unwrap()
+        .flatten()
+        .map(|x| x.path())
+        .filter(|x| x.extension().is_some_and(|ext| ext == "txt"))
+        .collect();
+    inputs.sort();
+    inputs
+}
+
+/// Parts that have a section in the sidecar, none if there is no sidecar.
+fn expected_parts(path: &Path) -> Vec<u8> {
+    let contents = fs::read_to_string(path).unwrap_or_default();
+
+    [(1, "[part1]"), (2, "[part2]")]
+        .into_iter()
+        .filter(|(_, header)| contents.lines().any(|x| x.trim_end() == *header))
+        .map(|(part, _)| part)
+        .collect()
+}
+
+/// A test named after the file and the part, and the variant if it is not
+/// the default, e.g. `example0_geometric_part2`.
+fn test(day: u8, input: &Path, variant: &str, part: u8) -> String {
+    let stem = input.file_stem().unwrap().to_string_lossy();
+    let mut name = identifier(&stem);
+
+    if !name.starts_with(|x: char| x.is_ascii_alphabetic()) {
+        name.insert_str(0, "file_");
+    }
+
+    if variant != DEFAULT_VARIANT {
+        name = format!("{name}_{}", identifier(variant));
+    }
+
+    let ignore = if stem == PUZZLE_INPUT {
+        "    #[ignore = \"runs on the puzzle input, see `cargo test -- --ignored`\"]\n"
+    } else {
+        ""
+    };
+
+    format!(
+        "    #[test]\n{ignore}    fn {name}_part{part}() {{\n        super::check({day}, {:?}, {variant:?}, {part});\n    }}\n",
+        input.display().to_string()
+    )
+}
+
+fn identifier(text: &str) -> String {
+    text.chars()
+        .map(|x| if x.is_ascii_alphanumeric() { x } else { '_' })
+        .collect()
+}
@@ -0,0 +1,65 @@
+use std::fmt::{self, Display};
+
+/// The answer to one part of a puzzle.
+///
+/// Solutions return their own result types, which convert into an answer: a
+/// whole number, a single line of text, or a rendering over several lines
+/// such as the screen of day 10.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    /// Wide enough for the results of every signed and unsigned 64-bit type.
+    Integer(i128),
+    Text(String),
+    Lines(Vec<String>),
+}
+
+impl Answer {
+    pub fn as_integer(&self) -> Option<i128> {
+        match self {
+            Answer::Integer(value) => Some(*value),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Integer(value) => write!(f, "{value}"),
+            Answer::Text(text) => write!(f, "{text}"),
+            Answer::Lines(lines) => {
+                for line in lines {
+                    writeln!(f, "{line}")?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+macro_rules! impl_from_integer {
+    ($($integer:ty),*) => {
+        $(
+            impl From<$integer> for Answer {
+                fn from(value: $integer) -> Self {
+                    Answer::Integer(value as i128)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_integer!(i32, i64, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(value.to_owned())
+    }
+}
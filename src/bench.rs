@@ -0,0 +1,92 @@
+use crate::{CancellationToken, SolutionError};
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+/// Summary of repeated timings of the same operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub iterations: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub p95: Duration,
+    pub stddev: Duration,
+}
+
+/// Times `routine` `iterations` times after `warm_up` untimed runs.
+///
+/// Every run gets a fresh value from `setup`, which is not part of the timing,
+/// and neither is dropping the value returned by `routine`. `setup` also gets
+/// the token of the run, which is cancelled once the run takes longer than
+/// `timeout`; the measurement then stops with [`SolutionError::Cancelled`].
+pub fn measure<S, T>(
+    warm_up: usize,
+    iterations: usize,
+    timeout: Option<Duration>,
+    mut setup: impl FnMut(CancellationToken) -> S,
+    mut routine: impl FnMut(S) -> T,
+) -> Result<Stats, SolutionError> {
+    let mut run = || {
+        let cancellation = CancellationToken::new();
+        let timer = timeout.map(|x| cancellation.cancel_after(x));
+        let input = setup(cancellation.clone());
+        let start = Instant::now();
+        let output = black_box(routine(input));
+        let elapsed = start.elapsed();
+        drop(timer);
+        drop(output);
+        cancellation.check().map(|_| elapsed)
+    };
+
+    for _ in 0..warm_up {
+        run()?;
+    }
+
+    let samples = (0..iterations.max(1))
+        .map(|_| run())
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(Stats::from_samples(&samples).unwrap())
+}
+
+impl Stats {
+    /// Computes the statistics of a set of timings, or `None` if it is empty.
+    ///
+    /// The p95 uses the nearest-rank method and the standard deviation is the
+    /// sample standard deviation.
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let n = sorted.len();
+
+        let median = if n.is_multiple_of(2) {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2
+        } else {
+            sorted[n / 2]
+        };
+        let p95 = sorted[(n * 95).div_ceil(100) - 1];
+
+        let nanos: Vec<f64> = sorted.iter().map(|x| x.as_nanos() as f64).collect();
+        let mean = nanos.iter().sum::<f64>() / n as f64;
+        let variance = if n > 1 {
+            nanos.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1) as f64
+        } else {
+            0.0
+        };
+
+        Some(Self {
+            iterations: n,
+            min: sorted[0],
+            median,
+            mean: Duration::from_nanos(mean.round() as u64),
+            p95,
+            stddev: Duration::from_nanos(variance.sqrt().round() as u64),
+        })
+    }
+}
@@ -0,0 +1,65 @@
+use crate::SolutionError;
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, RecvTimeoutError},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+/// Asks a running solution to stop early.
+///
+/// Solutions that can run for a long time check the token in their hot loops
+/// and return [`SolutionError::Cancelled`] once it is cancelled. Clones share
+/// the same state, so the token can be handed to worker threads.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+/// Cancels its token when the timeout passes, unless it is dropped before.
+#[derive(Debug)]
+pub struct CancelTimer {
+    _stop: mpsc::Sender<()>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Fails with [`SolutionError::Cancelled`] if the token was cancelled.
+    /// Cheap enough to call on every iteration.
+    pub fn check(&self) -> Result<(), SolutionError> {
+        if self.is_cancelled() {
+            Err(SolutionError::Cancelled)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Cancels the token from a background thread once `timeout` has passed.
+    /// Dropping the returned timer stops it.
+    pub fn cancel_after(&self, timeout: Duration) -> CancelTimer {
+        let (stop, stopped) = mpsc::channel::<()>();
+        let token = self.clone();
+
+        thread::spawn(move || {
+            if let Err(RecvTimeoutError::Timeout) = stopped.recv_timeout(timeout) {
+                token.cancel();
+            }
+        });
+
+        CancelTimer { _stop: stop }
+    }
+}
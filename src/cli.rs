@@ -0,0 +1,10 @@
+pub mod history;
+pub mod input;
+pub mod job;
+pub mod memory;
+pub mod output;
+pub mod report;
+pub mod scaffold;
+#[cfg(feature = "server")]
+pub mod server;
+pub mod watch;
@@ -0,0 +1,231 @@
+use super::report::{self, FileReport, Status};
+use advent_of_code_2022::registry;
+use color_eyre::eyre::{eyre, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fs::{self, OpenOptions},
+    io::{BufRead, BufReader, Write},
+    path::Path,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+pub const DEFAULT_PATH: &str = "target/history.jsonl";
+
+/// One run as stored in the history, a JSON-lines file with one run per line.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Run {
+    /// Seconds since the Unix epoch.
+    pub timestamp: u64,
+    /// Name to compare against later, e.g. a branch or a version.
+    pub label: Option<String>,
+    pub results: Vec<Entry>,
+}
+
+/// The result of one part on one input file. The solve time is the median
+/// when the run was benchmarked.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Entry {
+    pub day: u8,
+    pub file: String,
+    /// Runs recorded before variants existed used the default one.
+    #[serde(default = "default_variant")]
+    pub variant: String,
+    pub part: u8,
+    pub status: Status,
+    pub answer: Option<String>,
+    pub parse_ns: u64,
+    pub solve_ns: u64,
+}
+
+impl Run {
+    pub fn new(reports: &[FileReport], label: Option<String>) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |x| x.as_secs());
+        let results = reports
+            .iter()
+            .flat_map(|r| {
+                let parse = r.parse_stats.map_or(r.parse_duration, |s| s.median);
+
+                r.parts.iter().map(move |p| Entry {
+                    day: r.day,
+                    file: r.file.clone(),
+                    variant: r.variant.to_owned(),
+                    part: p.part,
+                    status: p.status(),
+                    answer: p.result.as_ref().ok().cloned(),
+                    parse_ns: parse.as_nanos() as u64,
+                    solve_ns: p.stats.map_or(p.duration, |s| s.median).as_nanos() as u64,
+                })
+            })
+            .collect();
+
+        Self {
+            timestamp,
+            label,
+            results,
+        }
+    }
+}
+
+impl Entry {
+    /// What the entry is a result of, which runs are compared by.
+    fn key(&self) -> (u8, &str, &str, u8) {
+        (self.day, &self.file, &self.variant, self.part)
+    }
+}
+
+fn default_variant() -> String {
+    registry::DEFAULT_VARIANT.to_owned()
+}
+
+/// Appends a run to the history, creating the file and its directory if
+/// needed.
+pub fn append(path: &Path, run: &Run) -> Result<()> {
+    if let Some(directory) = path.parent().filter(|x| !x.as_os_str().is_empty()) {
+        fs::create_dir_all(directory)
+            .wrap_err(format!("error creating directory {}", directory.display()))?;
+    }
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .wrap_err(format!("error opening {}", path.display()))?;
+    let line = serde_json::to_string(run)?;
+    writeln!(file, "{line}").wrap_err(format!("error writing {}", path.display()))
+}
+
+pub fn load(path: &Path) -> Result<Vec<Run>> {
+    let file = fs::File::open(path).wrap_err(format!("error opening {}", path.display()))?;
+
+    BufReader::new(file)
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| line.as_ref().map_or(true, |x| !x.trim().is_empty()))
+        .map(|(i, line)| {
+            let line = line?;
+            serde_json::from_str(&line).wrap_err(format!(
+                "error parsing {} line {}",
+                path.display(),
+                i + 1
+            ))
+        })
+        .collect()
+}
+
+/// Compares every part of the latest run against the latest earlier run that
+/// has it, or against the latest other run with the `baseline` label. Prints a
+/// row for every part, and fails if a part got slower by more than
+/// `threshold` percent or its answer changed.
+pub fn compare(runs: &[Run], baseline: Option<&str>, threshold: f64) -> Result<()> {
+    let (current, earlier) = runs
+        .split_last()
+        .ok_or_else(|| eyre!("the history is empty"))?;
+    let candidates: Vec<_> = match baseline {
+        Some(label) => {
+            let index = earlier
+                .iter()
+                .rposition(|x| x.label.as_deref() == Some(label))
+                .ok_or_else(|| eyre!("no earlier run is labelled '{label}'"))?;
+            vec![(index, &earlier[index])]
+        }
+        None if earlier.is_empty() => {
+            return Err(eyre!("the history has a single run, nothing to compare"));
+        }
+        None => earlier.iter().enumerate().collect(),
+    };
+
+    // The latest result of every part among the candidates, with the index of
+    // its run.
+    let mut before = HashMap::new();
+    for &(index, run) in candidates.iter().rev() {
+        for entry in &run.results {
+            before.entry(entry.key()).or_insert((index, entry));
+        }
+    }
+
+    let mut rows = vec![];
+    let mut slower = 0;
+    let mut changed = 0;
+
+    for entry in &current.results {
+        let Some(&(index, old)) = before.get(&entry.key()) else {
+            rows.push(row(entry, "-", "-".to_owned(), "-".to_owned(), "new"));
+            continue;
+        };
+
+        let change = (entry.solve_ns as f64 / old.solve_ns.max(1) as f64 - 1.0) * 100.0;
+        let note = if entry.answer != old.answer {
+            changed += 1;
+            "ANSWER CHANGED"
+        } else if change > threshold {
+            slower += 1;
+            "SLOWER"
+        } else if change < -threshold {
+            "faster"
+        } else {
+            ""
+        };
+
+        rows.push(row(
+            entry,
+            &describe(&earlier[index], index),
+            format!("{:.2?}", Duration::from_nanos(old.solve_ns)),
+            format!("{change:+.1}%"),
+            note,
+        ));
+    }
+
+    match baseline {
+        Some(_) => {
+            let (index, previous) = candidates[0];
+            println!(
+                "Comparing {} against {}",
+                describe(current, earlier.len()),
+                describe(previous, index)
+            );
+        }
+        None => println!(
+            "Comparing {} against the latest earlier run of every part",
+            describe(current, earlier.len())
+        ),
+    }
+    report::print_table(
+        [
+            "day", "file", "variant", "part", "against", "before", "after", "change", "note",
+        ],
+        &rows,
+    );
+
+    if slower > 0 || changed > 0 {
+        return Err(eyre!(
+            "{slower} part(s) got more than {threshold}% slower, {changed} answer(s) changed"
+        ));
+    }
+
+    Ok(())
+}
+
+fn row(entry: &Entry, against: &str, before: String, change: String, note: &str) -> [String; 9] {
+    [
+        entry.day.to_string(),
+        entry.file.clone(),
+        entry.variant.clone(),
+        entry.part.to_string(),
+        against.to_owned(),
+        before,
+        format!("{:.2?}", Duration::from_nanos(entry.solve_ns)),
+        change,
+        note.to_owned(),
+    ]
+}
+
+/// Names a run by its position in the history, counting from 1.
+fn describe(run: &Run, index: usize) -> String {
+    match &run.label {
+        Some(label) => format!("run {} '{label}'", index + 1),
+        None => format!("run {}", index + 1),
+    }
+}
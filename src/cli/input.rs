@@ -0,0 +1,114 @@
+use advent_of_code_2022::{expected::ExpectedAnswers, Params};
+use color_eyre::eyre::{Context, Result};
+use std::{
+    fmt::{self, Display},
+    io::Read,
+    path::{Path, PathBuf},
+};
+
+/// Where a puzzle input is read from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    File(PathBuf),
+    Stdin,
+}
+
+impl InputSource {
+    /// Resolves an `--input` argument: `-` is stdin, a directory expands to
+    /// the `*.txt` files in it and anything else is a single file.
+    pub fn resolve(path: &Path) -> Result<Vec<Self>> {
+        if path == Path::new("-") {
+            Ok(vec![Self::Stdin])
+        } else if path.is_dir() {
+            Self::directory(path)
+        } else {
+            Ok(vec![Self::File(path.to_owned())])
+        }
+    }
+
+    /// All `*.txt` files in a directory, sorted by path.
+    pub fn directory(directory: &Path) -> Result<Vec<Self>> {
+        let mut inputs: Vec<_> = std::fs::read_dir(directory)
+            .wrap_err(format!(
+                "error reading input directory '{}'",
+                directory.display()
+            ))?
+            .flatten()
+            .filter(|x| x.file_type().is_ok_and(|t| t.is_file()))
+            .map(|x| x.path())
+            .filter(|x| x.extension().is_some_and(|ext| ext == "txt"))
+            .collect();
+        inputs.sort();
+
+        Ok(inputs.into_iter().map(Self::File).collect())
+    }
+
+    pub fn read(&self) -> Result<String> {
+        match self {
+            Self::File(path) => std::fs::read_to_string(path)
+                .wrap_err(format!("error reading file {}", path.display())),
+            Self::Stdin => {
+                let mut input = String::new();
+                std::io::stdin()
+                    .read_to_string(&mut input)
+                    .wrap_err("error reading stdin")?;
+                Ok(input)
+            }
+        }
+    }
+
+    /// The expected answers from the sidecar file, if there is one. Input
+    /// from stdin never has expected answers.
+    pub fn expected_answers(&self) -> Result<ExpectedAnswers> {
+        let Self::File(input_path) = self else {
+            return Ok(ExpectedAnswers::default());
+        };
+        let path = ExpectedAnswers::sidecar_path(input_path);
+
+        if !path.is_file() {
+            return Ok(ExpectedAnswers::default());
+        }
+
+        let contents = std::fs::read_to_string(&path)
+            .wrap_err(format!("error reading file {}", path.display()))?;
+        ExpectedAnswers::parse(&contents).wrap_err(format!("error parsing {}", path.display()))
+    }
+
+    /// The parameters from the sidecar file, if there is one. Input from
+    /// stdin never has parameters.
+    pub fn params(&self) -> Result<Params> {
+        let Self::File(input_path) = self else {
+            return Ok(Params::default());
+        };
+        let path = Params::sidecar_path(input_path);
+
+        if !path.is_file() {
+            return Ok(Params::default());
+        }
+
+        let contents = std::fs::read_to_string(&path)
+            .wrap_err(format!("error reading file {}", path.display()))?;
+        Params::parse(&contents).wrap_err(format!("error parsing {}", path.display()))
+    }
+
+    /// Short name used in summaries and records.
+    pub fn name(&self) -> String {
+        match self {
+            Self::File(path) => path
+                .file_name()
+                .unwrap_or(path.as_os_str())
+                .to_string_lossy()
+                .into_owned(),
+            Self::Stdin => "stdin".to_owned(),
+        }
+    }
+}
+
+impl Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::File(path) => write!(f, "{}", path.display()),
+            Self::Stdin => write!(f, "<stdin>"),
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use color_eyre::eyre::{Context, Result};
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    ops::ControlFlow,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc,
+    },
+};
+
+/// Output of a job, kept until the job is done so that jobs running in
+/// parallel print one after the other instead of interleaving.
+#[derive(Debug, Default)]
+pub struct JobOutput {
+    lines: Vec<Line>,
+}
+
+#[derive(Debug)]
+enum Line {
+    Stdout(String),
+    Stderr(String),
+}
+
+impl JobOutput {
+    pub fn out<D: Display>(&mut self, line: D) {
+        self.lines.push(Line::Stdout(line.to_string()));
+    }
+
+    pub fn err<D: Display>(&mut self, line: D) {
+        self.lines.push(Line::Stderr(line.to_string()));
+    }
+
+    /// Prints the lines to stdout and stderr, in the order they were added.
+    pub fn print(&self) {
+        for line in &self.lines {
+            match line {
+                Line::Stdout(line) => println!("{line}"),
+                Line::Stderr(line) => eprintln!("{line}"),
+            }
+        }
+    }
+}
+
+/// Runs `run` on every job using `threads` threads, and passes the results to
+/// `on_done` in the order of `jobs`, each as soon as it and every job before
+/// it are finished. Once `on_done` breaks, the jobs that have not started yet
+/// are skipped and the ones already running are waited for.
+///
+/// With a single thread the jobs run one by one on the current thread, so
+/// that solutions using rayon themselves still get the global thread pool.
+pub fn run_ordered<J, R>(
+    jobs: &[J],
+    threads: usize,
+    run: impl Fn(&J) -> R + Sync,
+    mut on_done: impl FnMut(R) -> ControlFlow<()>,
+) -> Result<()>
+where
+    J: Sync,
+    R: Send,
+{
+    if threads <= 1 {
+        for job in jobs {
+            if on_done(run(job)).is_break() {
+                break;
+            }
+        }
+
+        return Ok(());
+    }
+
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build()
+        .wrap_err("error creating the thread pool")?;
+    let run = &run;
+    let stopped = &AtomicBool::new(false);
+
+    pool.in_place_scope_fifo(|scope| {
+        let (sender, receiver) = mpsc::channel();
+
+        for (i, job) in jobs.iter().enumerate() {
+            let sender = sender.clone();
+            scope.spawn_fifo(move |_| {
+                if stopped.load(Ordering::Relaxed) {
+                    return;
+                }
+
+                // The receiver may be gone if `on_done` broke while this job ran.
+                let _ = sender.send((i, run(job)));
+            });
+        }
+
+        drop(sender);
+
+        let mut pending = BTreeMap::new();
+        let mut next = 0;
+
+        for (i, result) in receiver {
+            pending.insert(i, result);
+
+            while let Some(result) = pending.remove(&next) {
+                next += 1;
+
+                if on_done(result).is_break() {
+                    stopped.store(true, Ordering::Relaxed);
+                    return;
+                }
+            }
+        }
+    });
+
+    Ok(())
+}
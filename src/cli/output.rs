@@ -0,0 +1,126 @@
+use super::report::{FileReport, Status};
+use clap::ValueEnum;
+use color_eyre::eyre::{Context, Result};
+use itertools::Itertools;
+use serde::Serialize;
+use std::io::Write;
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Human-readable progress and a summary table.
+    Text,
+    /// A JSON array with one record per day, input file and part.
+    Json,
+    /// A CSV table with one row per day, input file and part.
+    Csv,
+}
+
+/// A flat result of running one part on one input file.
+///
+/// Durations are in nanoseconds. The statistics are only present when running
+/// with `--bench`, and the allocations only with the `count-allocations`
+/// feature.
+#[derive(Serialize, Debug)]
+pub struct Record<'a> {
+    day: u8,
+    file: &'a str,
+    variant: &'a str,
+    part: u8,
+    status: Status,
+    answer: Option<&'a str>,
+    error: Option<String>,
+    /// Variants that gave a different answer, separated by spaces.
+    mismatched_variants: Option<String>,
+    parse_ns: u128,
+    solve_ns: u128,
+    iterations: Option<usize>,
+    parse_min_ns: Option<u128>,
+    parse_median_ns: Option<u128>,
+    parse_mean_ns: Option<u128>,
+    parse_p95_ns: Option<u128>,
+    parse_stddev_ns: Option<u128>,
+    solve_min_ns: Option<u128>,
+    solve_median_ns: Option<u128>,
+    solve_mean_ns: Option<u128>,
+    solve_p95_ns: Option<u128>,
+    solve_stddev_ns: Option<u128>,
+    parse_allocations: Option<usize>,
+    parse_allocated_bytes: Option<usize>,
+    parse_peak_bytes: Option<usize>,
+    solve_allocations: Option<usize>,
+    solve_allocated_bytes: Option<usize>,
+    solve_peak_bytes: Option<usize>,
+}
+
+pub fn records(reports: &[FileReport]) -> Vec<Record<'_>> {
+    reports
+        .iter()
+        .flat_map(|r| {
+            r.parts.iter().map(|p| {
+                let parse = r.parse_stats.as_ref();
+                let solve = p.stats.as_ref();
+
+                Record {
+                    day: r.day,
+                    file: &r.file,
+                    variant: r.variant,
+                    part: p.part,
+                    status: p.status(),
+                    answer: p.result.as_deref().ok(),
+                    error: p.result.as_ref().err().map(|e| e.to_string()),
+                    mismatched_variants: Some(p.disagreements().map(|x| x.variant).join(" "))
+                        .filter(|x| !x.is_empty()),
+                    parse_ns: r.parse_duration.as_nanos(),
+                    solve_ns: p.duration.as_nanos(),
+                    iterations: solve.or(parse).map(|s| s.iterations),
+                    parse_min_ns: parse.map(|s| s.min.as_nanos()),
+                    parse_median_ns: parse.map(|s| s.median.as_nanos()),
+                    parse_mean_ns: parse.map(|s| s.mean.as_nanos()),
+                    parse_p95_ns: parse.map(|s| s.p95.as_nanos()),
+                    parse_stddev_ns: parse.map(|s| s.stddev.as_nanos()),
+                    solve_min_ns: solve.map(|s| s.min.as_nanos()),
+                    solve_median_ns: solve.map(|s| s.median.as_nanos()),
+                    solve_mean_ns: solve.map(|s| s.mean.as_nanos()),
+                    solve_p95_ns: solve.map(|s| s.p95.as_nanos()),
+                    solve_stddev_ns: solve.map(|s| s.stddev.as_nanos()),
+                    parse_allocations: r.parse_memory.map(|m| m.allocations),
+                    parse_allocated_bytes: r.parse_memory.map(|m| m.allocated_bytes),
+                    parse_peak_bytes: r.parse_memory.map(|m| m.peak_bytes),
+                    solve_allocations: p.memory.map(|m| m.allocations),
+                    solve_allocated_bytes: p.memory.map(|m| m.allocated_bytes),
+                    solve_peak_bytes: p.memory.map(|m| m.peak_bytes),
+                }
+            })
+        })
+        .collect()
+}
+
+/// Writes the reports in a machine-readable format. Does nothing for
+/// [`OutputFormat::Text`], which is printed while running.
+pub fn write_records<W: Write>(
+    format: OutputFormat,
+    reports: &[FileReport],
+    mut writer: W,
+) -> Result<()> {
+    let records = records(reports);
+
+    match format {
+        OutputFormat::Text => (),
+        OutputFormat::Json => {
+            serde_json::to_writer_pretty(&mut writer, &records)
+                .wrap_err("error writing JSON output")?;
+            writeln!(writer)?;
+        }
+        OutputFormat::Csv => {
+            let mut writer = csv::Writer::from_writer(writer);
+            for record in records {
+                writer
+                    .serialize(record)
+                    .wrap_err("error writing CSV output")?;
+            }
+            writer.flush()?;
+        }
+    }
+
+    Ok(())
+}
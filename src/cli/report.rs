@@ -0,0 +1,231 @@
+use super::memory::{self, MemoryStats};
+use advent_of_code_2022::{bench::Stats, expected::Verdict, SolutionError};
+use serde::{Deserialize, Serialize};
+use std::{fmt::Display, time::Duration};
+
+pub struct FileReport {
+    pub day: u8,
+    pub file: String,
+    pub variant: &'static str,
+    pub parse_duration: Duration,
+    pub parse_stats: Option<Stats>,
+    pub parse_memory: Option<MemoryStats>,
+    pub parts: Vec<PartReport>,
+}
+
+pub struct PartReport {
+    pub part: u8,
+    pub result: Result<String, SolutionError>,
+    pub duration: Duration,
+    pub stats: Option<Stats>,
+    pub memory: Option<MemoryStats>,
+    pub verdict: Option<Verdict>,
+    /// Answers of the other variants of the day, with `--cross-check`.
+    pub cross_checks: Vec<CrossCheck>,
+}
+
+/// Answer of another variant of a day on the same input.
+pub struct CrossCheck {
+    pub variant: &'static str,
+    pub result: Result<String, SolutionError>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    Pass,
+    Fail,
+    Error,
+    Timeout,
+    /// Variants of the day gave different answers.
+    Mismatch,
+    Unchecked,
+    /// The part is not implemented yet, or the puzzle has no such part.
+    Skipped,
+}
+
+impl PartReport {
+    pub fn status(&self) -> Status {
+        match (&self.result, &self.verdict) {
+            (Err(SolutionError::Cancelled), _) => Status::Timeout,
+            (Err(SolutionError::NotImplemented | SolutionError::NoSuchPart), _) => Status::Skipped,
+            (Err(_), _) => Status::Error,
+            _ if self.disagreements().next().is_some() => Status::Mismatch,
+            (_, Some(Verdict::Pass)) => Status::Pass,
+            (_, Some(Verdict::Fail(_))) => Status::Fail,
+            (_, None) => Status::Unchecked,
+        }
+    }
+
+    /// Cross-checked variants whose answer differs from this one.
+    pub fn disagreements(&self) -> impl Iterator<Item = &CrossCheck> {
+        self.cross_checks
+            .iter()
+            .filter(|x| x.result.as_ref().ok() != self.result.as_ref().ok())
+    }
+
+    fn answer_summary(&self) -> String {
+        let answer = match &self.result {
+            Ok(answer) => answer.trim_end(),
+            Err(e) => return e.to_string(),
+        };
+        let lines = answer.lines().count();
+
+        if lines > 1 {
+            format!("<{lines} lines>")
+        } else {
+            answer.to_owned()
+        }
+    }
+}
+
+pub fn print_summary(reports: &[FileReport]) {
+    let rows: Vec<[String; 7]> = reports
+        .iter()
+        .flat_map(|r| {
+            r.parts.iter().map(|p| {
+                [
+                    r.day.to_string(),
+                    r.file.clone(),
+                    p.part.to_string(),
+                    p.answer_summary(),
+                    format!("{:.2?}", r.parse_duration),
+                    format!("{:.2?}", p.duration),
+                    p.status().to_string(),
+                ]
+            })
+        })
+        .collect();
+
+    println!();
+    print_table(
+        ["day", "file", "part", "answer", "parse", "solve", "status"],
+        &rows,
+    );
+
+    let total_parse: Duration = reports.iter().map(|r| r.parse_duration).sum();
+    let total_solve: Duration = reports
+        .iter()
+        .flat_map(|r| r.parts.iter())
+        .map(|p| p.duration)
+        .sum();
+    println!("total parse: {total_parse:.2?}, total solve: {total_solve:.2?}");
+
+    print_memory_summary(reports);
+}
+
+/// Prints the allocations of every phase, if they were counted.
+fn print_memory_summary(reports: &[FileReport]) {
+    let rows: Vec<[String; 6]> = reports
+        .iter()
+        .flat_map(|r| {
+            let parse = r.parse_memory.map(|m| ("parse".to_owned(), m));
+            let parts = r
+                .parts
+                .iter()
+                .filter_map(|p| p.memory.map(|m| (format!("part {}", p.part), m)));
+
+            parse.into_iter().chain(parts).map(|(phase, m)| {
+                [
+                    r.day.to_string(),
+                    r.file.clone(),
+                    phase,
+                    m.allocations.to_string(),
+                    memory::format_bytes(m.allocated_bytes),
+                    memory::format_bytes(m.peak_bytes),
+                ]
+            })
+        })
+        .collect();
+
+    if rows.is_empty() {
+        return;
+    }
+
+    println!();
+    print_table(
+        ["day", "file", "phase", "allocations", "allocated", "peak"],
+        &rows,
+    );
+}
+
+pub fn print_table<const N: usize>(headers: [&str; N], rows: &[[String; N]]) {
+    println!("{}", format_table(headers, rows));
+}
+
+/// Lays out `rows` in columns under `headers`, without a trailing newline.
+pub fn format_table<const N: usize>(headers: [&str; N], rows: &[[String; N]]) -> String {
+    let mut widths = headers.map(str::len);
+    for row in rows.iter() {
+        for (width, cell) in widths.iter_mut().zip(row.iter()) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let format_row = |cells: &[&str]| {
+        let line: Vec<_> = cells
+            .iter()
+            .zip(widths.iter())
+            .map(|(cell, &width)| format!("{cell:width$}"))
+            .collect();
+        line.join(" | ").trim_end().to_owned()
+    };
+
+    let mut lines = vec![format_row(&headers)];
+    lines.push(
+        widths
+            .iter()
+            .map(|&w| "-".repeat(w))
+            .collect::<Vec<_>>()
+            .join("-+-"),
+    );
+    for row in rows.iter() {
+        lines.push(format_row(&row.each_ref().map(String::as_str)));
+    }
+
+    lines.join("\n")
+}
+
+pub fn stats_row(phase: String, stats: &Stats) -> [String; 6] {
+    [
+        phase,
+        format!("{:.2?}", stats.min),
+        format!("{:.2?}", stats.median),
+        format!("{:.2?}", stats.mean),
+        format!("{:.2?}", stats.p95),
+        format!("{:.2?}", stats.stddev),
+    ]
+}
+
+/// A row of the benchmark table for a phase whose measurement failed.
+pub fn failed_row(phase: String, error: &SolutionError) -> [String; 6] {
+    let status = match error {
+        SolutionError::Cancelled => Status::Timeout,
+        _ => Status::Error,
+    };
+
+    [
+        phase,
+        status.to_string(),
+        "-".into(),
+        "-".into(),
+        "-".into(),
+        "-".into(),
+    ]
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let status = match self {
+            Status::Pass => "PASS",
+            Status::Fail => "FAIL",
+            Status::Error => "ERROR",
+            Status::Timeout => "TIMEOUT",
+            Status::Mismatch => "MISMATCH",
+            Status::Unchecked => "-",
+            Status::Skipped => "SKIPPED",
+        };
+
+        write!(f, "{status}")
+    }
+}
@@ -0,0 +1,191 @@
+use color_eyre::eyre::{eyre, Context, Result};
+use std::{fs, path::Path};
+
+const TEMPLATE: &str = include_str!("scaffold/day.rs.template");
+const REGISTRY_START: &str = "registry! {";
+const VARIANTS_START: &str = "pub const VARIANTS: &[(u8, &[&str])] = &[";
+const PLACEHOLDER_FILES: [&str; 2] = ["example0.txt", "example0.expected"];
+
+/// Adds a new day: generates its module from the template, declares and
+/// registers it, lists its default variant, and creates its input directory
+/// with an empty example and expected answers.
+///
+/// Every edit is prepared before anything is written, so a day that already
+/// exists leaves the tree untouched.
+pub fn scaffold(day: u8, name: &str, source_root: &Path, input_root: &Path) -> Result<()> {
+    let module_path = source_root.join("days").join(format!("day{day}.rs"));
+    let days_path = source_root.join("days.rs");
+    let registry_path = source_root.join("registry.rs");
+    let variants_path = source_root.join("registry").join("variants.rs");
+
+    if module_path.exists() {
+        return Err(eyre!("{} already exists", module_path.display()));
+    }
+
+    let days = add_module(&read(&days_path)?, day)
+        .wrap_err(format!("error editing {}", days_path.display()))?;
+    let registry = add_registration(&read(&registry_path)?, day, name)
+        .wrap_err(format!("error editing {}", registry_path.display()))?;
+    let variants = add_variants(&read(&variants_path)?, day)
+        .wrap_err(format!("error editing {}", variants_path.display()))?;
+
+    write(&module_path, &TEMPLATE.replace("{{day}}", &day.to_string()))?;
+    write(&days_path, &days)?;
+    write(&registry_path, &registry)?;
+    write(&variants_path, &variants)?;
+
+    let input_directory = input_root.join(format!("day{day}"));
+    fs::create_dir_all(&input_directory).wrap_err(format!(
+        "error creating directory {}",
+        input_directory.display()
+    ))?;
+
+    for file in PLACEHOLDER_FILES {
+        let path = input_directory.join(file);
+
+        if !path.exists() {
+            write(&path, "")?;
+        }
+    }
+
+    println!("Created {}", module_path.display());
+    println!("Registered day {day} in {}", registry_path.display());
+    println!("Listed its default variant in {}", variants_path.display());
+    println!("Created {}", input_directory.display());
+
+    Ok(())
+}
+
+/// Declares and re-exports the module of `day`, keeping both lists sorted the
+/// way rustfmt sorts them and leaving the other lines as they are.
+fn add_module(days: &str, day: u8) -> Result<String> {
+    let module = format!("day{day}");
+    let mut lines: Vec<_> = days.lines().map(str::to_owned).collect();
+
+    if lines.iter().any(|x| declared_module(x) == Some(&module)) {
+        return Err(eyre!("module {module} is already declared"));
+    }
+
+    insert_sorted(
+        &mut lines,
+        declared_module,
+        &module,
+        format!("mod {module};"),
+    );
+    insert_sorted(
+        &mut lines,
+        exported_module,
+        &module,
+        format!("pub use {module}::*;"),
+    );
+
+    Ok(lines.join("\n") + "\n")
+}
+
+fn declared_module(line: &str) -> Option<&str> {
+    line.strip_prefix("mod ")?.strip_suffix(';')
+}
+
+fn exported_module(line: &str) -> Option<&str> {
+    line.strip_prefix("pub use ")?.split("::").next()
+}
+
+/// Inserts `line` before the first of the lines naming a module after
+/// `module`, or after the last of them.
+fn insert_sorted(
+    lines: &mut Vec<String>,
+    name: fn(&str) -> Option<&str>,
+    module: &str,
+    line: String,
+) {
+    let named: Vec<_> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, x)| name(x).map(|x| (i, x > module)))
+        .collect();
+    let position = match named.iter().find(|(_, after)| *after) {
+        Some(&(i, _)) => i,
+        None => named.last().map_or(lines.len(), |&(i, _)| i + 1),
+    };
+
+    lines.insert(position, line);
+}
+
+/// Inserts the registration of `day` into the `registry!` invocation, in
+/// order of days.
+fn add_registration(registry: &str, day: u8, name: &str) -> Result<String> {
+    insert_day(
+        registry,
+        REGISTRY_START,
+        "}",
+        day,
+        |x| x.split_once("=>").and_then(|(x, _)| x.trim().parse().ok()),
+        format!("    {day} => Day{day}, {name:?};"),
+    )
+}
+
+/// Lists the default variant of `day` in the table of variants, in order of
+/// days.
+fn add_variants(variants: &str, day: u8) -> Result<String> {
+    insert_day(
+        variants,
+        VARIANTS_START,
+        "];",
+        day,
+        |x| x.trim().strip_prefix('(')?.split_once(',')?.0.parse().ok(),
+        format!("    ({day}, &[DEFAULT_VARIANT]),"),
+    )
+}
+
+/// Inserts `line` into the block of lines from the one equal to `start` to
+/// the next one equal to `end`, before the first line for a later day as
+/// given by `day_of`.
+fn insert_day(
+    text: &str,
+    start: &str,
+    end: &str,
+    day: u8,
+    day_of: fn(&str) -> Option<u8>,
+    line: String,
+) -> Result<String> {
+    let mut lines: Vec<_> = text.lines().map(str::to_owned).collect();
+    let start_index = lines
+        .iter()
+        .position(|x| x.trim_end() == start)
+        .ok_or_else(|| eyre!("could not find '{start}'"))?;
+    let end_index = lines[start_index..]
+        .iter()
+        .position(|x| x.trim_end() == end)
+        .map(|x| start_index + x)
+        .ok_or_else(|| eyre!("could not find the end of '{start}'"))?;
+
+    let mut position = end_index;
+
+    for (i, x) in lines
+        .iter()
+        .enumerate()
+        .take(end_index)
+        .skip(start_index + 1)
+    {
+        match day_of(x) {
+            Some(x) if x == day => return Err(eyre!("day {day} is already listed")),
+            Some(x) if x > day => {
+                position = i;
+                break;
+            }
+            _ => (),
+        }
+    }
+
+    lines.insert(position, line);
+
+    Ok(lines.join("\n") + "\n")
+}
+
+fn read(path: &Path) -> Result<String> {
+    fs::read_to_string(path).wrap_err(format!("error reading file {}", path.display()))
+}
+
+fn write(path: &Path, contents: &str) -> Result<()> {
+    fs::write(path, contents).wrap_err(format!("error writing file {}", path.display()))
+}
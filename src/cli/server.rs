@@ -0,0 +1,327 @@
+use advent_of_code_2022::{
+    params, registry, Answer, CancellationToken, Error, Params, SolutionError,
+};
+use color_eyre::eyre::{Context, Result};
+use rayon::ThreadPoolBuilder;
+use serde_json::{json, Value};
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    panic::{self, AssertUnwindSafe},
+    time::{Duration, Instant},
+};
+use tracing::{error, info, info_span, warn};
+
+/// Largest request body accepted, well above the size of any puzzle input.
+const MAX_BODY_BYTES: usize = 1 << 20;
+/// How long reading a request or writing a response may stall before the
+/// connection is dropped, so that a client that stops sending does not hold
+/// on to a thread.
+const IO_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Serves the solutions over HTTP, handling up to `jobs` connections at a time
+/// on a thread pool, or one per CPU if not given. Other connections wait for
+/// a free thread.
+///
+/// - `GET /days` lists the registered days.
+/// - `POST /solve/{day}/{part}` solves a part on the input in the body, with
+///   parameters of the solution in the query, e.g. `?target_y=10`.
+///
+/// Responses are JSON and allow any origin, so that a page served from
+/// another port on localhost can call the server. A solution that panics gets
+/// a 500 response instead of taking the server down.
+pub fn serve(address: &str, jobs: Option<usize>, timeout: Option<Duration>) -> Result<()> {
+    let pool = ThreadPoolBuilder::new()
+        .num_threads(jobs.unwrap_or(0))
+        .build()
+        .wrap_err("error creating the thread pool")?;
+    let listener = TcpListener::bind(address).wrap_err(format!("error listening on {address}"))?;
+    info!("Listening on http://{}", listener.local_addr()?);
+
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => pool.spawn(move || handle(stream, timeout)),
+            Err(e) => warn!("error accepting a connection: {e}"),
+        }
+    }
+
+    Ok(())
+}
+
+struct Request {
+    method: String,
+    path: String,
+    /// Decoded `key=value` assignments of the query.
+    query: Vec<String>,
+    body: String,
+}
+
+struct Response {
+    status: u16,
+    body: Option<Value>,
+}
+
+fn handle(mut stream: TcpStream, timeout: Option<Duration>) {
+    let timeouts = stream
+        .set_read_timeout(Some(IO_TIMEOUT))
+        .and_then(|_| stream.set_write_timeout(Some(IO_TIMEOUT)));
+    if let Err(e) = timeouts {
+        warn!("error setting up a connection: {e}");
+        return;
+    }
+
+    let response = match read_request(&stream) {
+        Ok(request) => info_span!("request", method = %request.method, path = %request.path)
+            .in_scope(|| {
+                // The panic message itself is printed by the panic hook.
+                panic::catch_unwind(AssertUnwindSafe(|| route(&request, timeout))).unwrap_or_else(
+                    |_| {
+                        error!("the request panicked");
+                        Response::error(500, "the solution panicked")
+                    },
+                )
+            }),
+        Err(message) => Response::error(400, message),
+    };
+
+    if let Err(e) = response.write_to(&mut stream) {
+        warn!("error writing a response: {e}");
+    }
+}
+
+fn read_request(stream: &TcpStream) -> Result<Request, String> {
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    reader
+        .read_line(&mut line)
+        .map_err(|e| format!("error reading the request: {e}"))?;
+
+    let mut request_line = line.split_whitespace();
+    let (Some(method), Some(target)) = (request_line.next(), request_line.next()) else {
+        return Err(format!("invalid request line '{}'", line.trim_end()));
+    };
+    let (path, query) = match target.split_once('?') {
+        Some((path, query)) => (
+            path,
+            query
+                .split('&')
+                .map(percent_decode)
+                .collect::<Result<_, _>>()?,
+        ),
+        None => (target, vec![]),
+    };
+    let (method, path) = (method.to_owned(), path.to_owned());
+
+    let mut content_length = 0;
+
+    loop {
+        line.clear();
+        reader
+            .read_line(&mut line)
+            .map_err(|e| format!("error reading the headers: {e}"))?;
+
+        let header = line.trim_end();
+        if header.is_empty() {
+            break;
+        }
+
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value
+                    .trim()
+                    .parse()
+                    .map_err(|e| format!("invalid content length '{}': {e}", value.trim()))?;
+            }
+        }
+    }
+
+    if content_length > MAX_BODY_BYTES {
+        return Err(format!("the body is larger than {MAX_BODY_BYTES} bytes"));
+    }
+
+    let mut body = vec![0; content_length];
+    reader
+        .read_exact(&mut body)
+        .map_err(|e| format!("error reading the body: {e}"))?;
+    let body = String::from_utf8(body).map_err(|_| "the body is not UTF-8".to_owned())?;
+
+    Ok(Request {
+        method,
+        path,
+        query,
+        body,
+    })
+}
+
+/// Decodes the `%XX` escapes of a query component, and `+` as a space the way
+/// HTML forms encode it.
+fn percent_decode(component: &str) -> Result<String, String> {
+    let hex_digit = |x: &u8| (*x as char).to_digit(16);
+    let mut bytes = Vec::with_capacity(component.len());
+    let mut rest = component.as_bytes();
+
+    while let Some((&byte, tail)) = rest.split_first() {
+        rest = tail;
+
+        match byte {
+            b'%' => match (
+                tail.first().and_then(hex_digit),
+                tail.get(1).and_then(hex_digit),
+            ) {
+                (Some(high), Some(low)) => {
+                    bytes.push((high * 16 + low) as u8);
+                    rest = &tail[2..];
+                }
+                _ => return Err(format!("invalid percent escape in '{component}'")),
+            },
+            b'+' => bytes.push(b' '),
+            _ => bytes.push(byte),
+        }
+    }
+
+    String::from_utf8(bytes).map_err(|_| format!("'{component}' does not decode to UTF-8"))
+}
+
+fn route(request: &Request, timeout: Option<Duration>) -> Response {
+    let segments: Vec<_> = request.path.split('/').filter(|x| !x.is_empty()).collect();
+
+    match (request.method.as_str(), segments.as_slice()) {
+        ("OPTIONS", _) => Response {
+            status: 204,
+            body: None,
+        },
+        ("GET", ["days"]) => Response::ok(list_days()),
+        ("POST", ["solve", day, part]) => match (day.parse(), part.parse()) {
+            (Ok(day), Ok(part)) => match solve(day, part, request, timeout) {
+                Ok(result) => Response::ok(result),
+                Err(e) => Response::from_error(&e),
+            },
+            _ => Response::error(404, format!("no such endpoint '{}'", request.path)),
+        },
+        (_, ["days"] | ["solve", _, _]) => Response::error(405, "method not allowed"),
+        _ => Response::error(404, format!("no such endpoint '{}'", request.path)),
+    }
+}
+
+fn list_days() -> Value {
+    registry::all()
+        .iter()
+        .map(|x| {
+            json!({
+                "day": x.day,
+                "name": x.name,
+                "variants": x.variant_names().collect::<Vec<_>>(),
+            })
+        })
+        .collect()
+}
+
+fn solve(day: u8, part: u8, request: &Request, timeout: Option<Duration>) -> Result<Value, Error> {
+    let registration = registry::get(day).ok_or(Error::UnknownDay(day))?;
+
+    if !(1..=2).contains(&part) {
+        return Err(Error::UnknownPart(part));
+    }
+
+    let mut params = Params::default();
+    for assignment in &request.query {
+        let (key, value) = params::parse_assignment(assignment)?;
+        params.insert(key, value);
+    }
+
+    let mut solution = registration.create();
+    solution.configure(&mut params)?;
+    params.ensure_all_applied()?;
+
+    let cancellation = CancellationToken::new();
+    solution.set_cancellation(cancellation.clone());
+
+    let start = Instant::now();
+    let data = solution.parse(&request.body)?;
+    let parse_duration = start.elapsed();
+
+    let timer = timeout.map(|x| cancellation.cancel_after(x));
+    let start = Instant::now();
+    let result = match part {
+        1 => solution.run_part_1(data.as_ref())?,
+        _ => solution.run_part_2(data.as_ref())?,
+    };
+    let solve_duration = start.elapsed();
+    drop(timer);
+
+    Ok(json!({
+        "day": day,
+        "part": part,
+        "answer": answer_json(&result),
+        "parse_ns": parse_duration.as_nanos() as u64,
+        "solve_ns": solve_duration.as_nanos() as u64,
+    }))
+}
+
+fn answer_json(answer: &Answer) -> Value {
+    match answer {
+        Answer::Integer(value) => json!({ "kind": "integer", "value": value }),
+        Answer::Text(text) => json!({ "kind": "text", "value": text }),
+        Answer::Lines(lines) => json!({ "kind": "lines", "value": lines }),
+    }
+}
+
+impl Response {
+    fn ok(body: Value) -> Self {
+        Self {
+            status: 200,
+            body: Some(body),
+        }
+    }
+
+    fn from_error(error: &Error) -> Self {
+        match error {
+            Error::UnknownDay(_)
+            | Error::UnknownPart(_)
+            | Error::Solution(SolutionError::NoSuchPart) => Self::error(404, error),
+            Error::Params(_) => Self::error(400, error),
+            Error::Solution(SolutionError::Cancelled) => Self::error(504, "the part timed out"),
+            Error::Solution(SolutionError::NotImplemented) => Self::error(501, error),
+            Error::Parse(_) | Error::Solution(_) => Self::error(422, error),
+        }
+    }
+
+    fn error<D: ToString>(status: u16, message: D) -> Self {
+        Self {
+            status,
+            body: Some(json!({ "error": message.to_string() })),
+        }
+    }
+
+    fn write_to(&self, stream: &mut TcpStream) -> std::io::Result<()> {
+        let body = self.body.as_ref().map_or(String::new(), Value::to_string);
+        let reason = match self.status {
+            200 => "OK",
+            204 => "No Content",
+            400 => "Bad Request",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            422 => "Unprocessable Entity",
+            500 => "Internal Server Error",
+            501 => "Not Implemented",
+            504 => "Gateway Timeout",
+            _ => "",
+        };
+
+        write!(
+            stream,
+            "HTTP/1.1 {} {reason}\r\n\
+             Content-Type: application/json\r\n\
+             Content-Length: {}\r\n\
+             Access-Control-Allow-Origin: *\r\n\
+             Access-Control-Allow-Methods: GET, POST, OPTIONS\r\n\
+             Access-Control-Allow-Headers: Content-Type\r\n\
+             Connection: close\r\n\
+             \r\n\
+             {body}",
+            self.status,
+            body.len()
+        )?;
+        stream.flush()
+    }
+}
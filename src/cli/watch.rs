@@ -0,0 +1,86 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Size and modification time of every watched file.
+type Snapshot = BTreeMap<PathBuf, (u64, Option<SystemTime>)>;
+
+/// Detects changes to files by polling their metadata, so it works without
+/// any platform-specific notification mechanism.
+pub struct Watcher {
+    paths: Vec<PathBuf>,
+    snapshot: Snapshot,
+}
+
+impl Watcher {
+    /// Watches the given files and the files directly inside the given
+    /// directories, including ones that are added later.
+    pub fn new(paths: Vec<PathBuf>) -> Self {
+        let snapshot = snapshot(&paths);
+        Self { paths, snapshot }
+    }
+
+    pub fn paths(&self) -> &[PathBuf] {
+        &self.paths
+    }
+
+    /// Blocks until a file has been added, removed or modified since the
+    /// previous call, and then until the files stop changing, so that a
+    /// save in progress is not picked up halfway.
+    pub fn wait_for_change(&mut self) {
+        loop {
+            thread::sleep(POLL_INTERVAL);
+            let current = snapshot(&self.paths);
+
+            if current != self.snapshot {
+                self.snapshot = current;
+                break;
+            }
+        }
+
+        loop {
+            thread::sleep(POLL_INTERVAL);
+            let current = snapshot(&self.paths);
+
+            if current == self.snapshot {
+                break;
+            }
+
+            self.snapshot = current;
+        }
+    }
+}
+
+fn snapshot(paths: &[PathBuf]) -> Snapshot {
+    let mut snapshot = Snapshot::new();
+
+    for path in paths {
+        if path.is_dir() {
+            let files = fs::read_dir(path)
+                .into_iter()
+                .flatten()
+                .flatten()
+                .filter(|x| x.file_type().is_ok_and(|t| t.is_file()));
+
+            for file in files {
+                insert(&mut snapshot, &file.path());
+            }
+        } else {
+            insert(&mut snapshot, path);
+        }
+    }
+
+    snapshot
+}
+
+fn insert(snapshot: &mut Snapshot, path: &Path) {
+    if let Ok(metadata) = fs::metadata(path) {
+        snapshot.insert(path.to_owned(), (metadata.len(), metadata.modified().ok()));
+    }
+}
@@ -24,15 +24,17 @@ mod day7;
 mod day8;
 mod day9;
 
+// The `Command` inputs of days 7 and 10 and the `Move` input of day 17 are
+// left out, as their names are taken by days 5 and 9.
 pub use day1::*;
-pub use day10::*;
+pub use day10::{Crt, Day10};
 pub use day11::*;
 pub use day12::*;
 pub use day13::*;
 pub use day14::*;
 pub use day15::*;
 pub use day16::*;
-pub use day17::*;
+pub use day17::Day17;
 pub use day18::*;
 pub use day19::*;
 pub use day2::*;
@@ -46,6 +48,6 @@ pub use day3::*;
 pub use day4::*;
 pub use day5::*;
 pub use day6::*;
-pub use day7::*;
+pub use day7::{Day7, Directory, DirectoryEntry};
 pub use day8::*;
 pub use day9::*;
@@ -1,8 +1,8 @@
 use crate::iterators::SplitIteratorExt;
-use crate::parsing::{integer, line_separated};
-use crate::Solution;
+use crate::parsing::{all_lines, integer, ParseError, ParseFinish};
+use crate::{Solution, SolutionError};
 use itertools::Itertools;
-use nom::combinator::{all_consuming, opt};
+use nom::combinator::opt;
 
 #[derive(Default)]
 pub struct Day1 {}
@@ -13,11 +13,8 @@ impl Solution for Day1 {
 
     type Input = Vec<Vec<u64>>;
 
-    fn parse<'a>(
-        &mut self,
-        input: &'a str,
-    ) -> Result<Self::Input, nom::Err<nom::error::Error<&'a str>>> {
-        let list = all_consuming(line_separated(opt(integer)))(input)?.1;
+    fn parse(&mut self, input: &str) -> Result<Self::Input, ParseError> {
+        let list = all_lines(opt(integer))(input).finish_parse(input)?;
 
         Ok(list
             .into_iter()
@@ -26,16 +23,17 @@ impl Solution for Day1 {
             .collect())
     }
 
-    fn run_part_1(&mut self, data: &Self::Input) -> Self::Part1Result {
-        data.iter().map(|xs| xs.iter().sum()).max().unwrap_or(0)
+    fn run_part_1(&mut self, data: &Self::Input) -> Result<Self::Part1Result, SolutionError> {
+        Ok(data.iter().map(|xs| xs.iter().sum()).max().unwrap_or(0))
     }
 
-    fn run_part_2(&mut self, data: &Self::Input) -> Self::Part2Result {
-        data.iter()
+    fn run_part_2(&mut self, data: &Self::Input) -> Result<Self::Part2Result, SolutionError> {
+        Ok(data
+            .iter()
             .map(|xs| xs.iter().sum::<u64>())
             .sorted()
             .rev()
             .take(3)
-            .sum()
+            .sum())
     }
 }
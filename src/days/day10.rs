@@ -1,16 +1,10 @@
 use std::fmt::Display;
 
-use nom::{
-    branch::alt,
-    bytes::complete::tag,
-    combinator::{all_consuming, map},
-    sequence::preceded,
-    IResult,
-};
+use nom::{branch::alt, bytes::complete::tag, combinator::map, error::context, sequence::preceded};
 
 use crate::{
-    parsing::{integer, line_separated},
-    Solution,
+    parsing::{all_lines, integer, IResult, ParseError, ParseFinish},
+    Answer, Solution, SolutionError,
 };
 
 #[derive(Default)]
@@ -22,14 +16,11 @@ impl Solution for Day10 {
 
     type Input = Vec<Command>;
 
-    fn parse<'a>(
-        &mut self,
-        input: &'a str,
-    ) -> Result<Self::Input, nom::Err<nom::error::Error<&'a str>>> {
-        all_consuming(line_separated(parse_command))(input).map(|x| x.1)
+    fn parse(&mut self, input: &str) -> Result<Self::Input, ParseError> {
+        all_lines(parse_command)(input).finish_parse(input)
     }
 
-    fn run_part_1(&mut self, data: &Self::Input) -> Self::Part1Result {
+    fn run_part_1(&mut self, data: &Self::Input) -> Result<Self::Part1Result, SolutionError> {
         let cmd_details = data.iter().scan((1, 0), |(x, t), cmd| {
             let t_start = *t;
             let x_start = *x;
@@ -58,10 +49,10 @@ impl Solution for Day10 {
             }
         }
 
-        signal.total
+        Ok(signal.total)
     }
 
-    fn run_part_2(&mut self, data: &Self::Input) -> Self::Part2Result {
+    fn run_part_2(&mut self, data: &Self::Input) -> Result<Self::Part2Result, SolutionError> {
         let mut crt = Crt::new(3);
         let mut x = 0;
 
@@ -77,7 +68,7 @@ impl Solution for Day10 {
             }
         }
 
-        crt
+        Ok(crt)
     }
 }
 
@@ -209,8 +200,15 @@ impl Display for Crt {
     }
 }
 
+impl From<Crt> for Answer {
+    fn from(value: Crt) -> Self {
+        let rows = value.buffer.chunks(Crt::WIDTH);
+        Answer::Lines(rows.map(|x| x.iter().collect()).collect())
+    }
+}
+
 fn parse_command(input: &str) -> IResult<&str, Command> {
-    alt((parse_addx, parse_noop))(input)
+    context("instruction", alt((parse_addx, parse_noop)))(input)
 }
 
 fn parse_addx(input: &str) -> IResult<&str, Command> {
@@ -1,20 +1,31 @@
 use crate::{
-    parsing::{integer, line_separated},
-    Solution,
+    parsing::{all_lines, integer, IResult, ParseError, ParseFinish},
+    ParamError, Params, Solution, SolutionError,
 };
 use itertools::Itertools;
 use nom::{
     branch::alt,
     bytes::complete::tag,
     character::complete::{char, newline, space0},
-    combinator::{all_consuming, map, opt},
+    combinator::{map, opt},
+    error::context,
     multi::separated_list0,
     sequence::{delimited, preceded, separated_pair, tuple},
-    IResult,
 };
 
-#[derive(Default)]
-pub struct Day11 {}
+pub struct Day11 {
+    part_1_rounds: usize,
+    part_2_rounds: usize,
+}
+
+impl Default for Day11 {
+    fn default() -> Self {
+        Self {
+            part_1_rounds: 20,
+            part_2_rounds: 10_000,
+        }
+    }
+}
 
 impl Solution for Day11 {
     type Part1Result = usize;
@@ -22,23 +33,25 @@ impl Solution for Day11 {
 
     type Input = Vec<MonkeyDescription>;
 
-    fn parse<'a>(
-        &mut self,
-        input: &'a str,
-    ) -> Result<Self::Input, nom::Err<nom::error::Error<&'a str>>> {
-        all_consuming(line_separated(monkey))(input).map(|x| x.1)
+    fn configure(&mut self, params: &mut Params) -> Result<(), ParamError> {
+        params.apply("part_1_rounds", &mut self.part_1_rounds)?;
+        params.apply("part_2_rounds", &mut self.part_2_rounds)
     }
 
-    fn run_part_1(&mut self, data: &Self::Input) -> Self::Part1Result {
+    fn parse(&mut self, input: &str) -> Result<Self::Input, ParseError> {
+        all_lines(monkey)(input).finish_parse(input)
+    }
+
+    fn run_part_1(&mut self, data: &Self::Input) -> Result<Self::Part1Result, SolutionError> {
         let mut monkey_business = MonkeyBusiness::<ItemWithDivision>::new(data);
-        monkey_business.run_for(20);
-        monkey_business.value()
+        monkey_business.run_for(self.part_1_rounds);
+        Ok(monkey_business.value())
     }
 
-    fn run_part_2(&mut self, data: &Self::Input) -> Self::Part2Result {
+    fn run_part_2(&mut self, data: &Self::Input) -> Result<Self::Part2Result, SolutionError> {
         let mut monkey_business = MonkeyBusiness::<SimpleItem>::new(data);
-        monkey_business.run_for(10_000);
-        monkey_business.value()
+        monkey_business.run_for(self.part_2_rounds);
+        Ok(monkey_business.value())
     }
 }
 
@@ -182,7 +195,7 @@ impl Item for ItemWithDivision {
     }
 
     fn test(&self, test: Int) -> bool {
-        self.worry % test == 0
+        self.worry.is_multiple_of(test)
     }
 }
 
@@ -208,7 +221,7 @@ impl Item for SimpleItem {
     }
 
     fn test(&self, test: Int) -> bool {
-        self.worry % test == 0
+        self.worry.is_multiple_of(test)
     }
 }
 
@@ -319,10 +332,13 @@ fn multiply(input: &str) -> IResult<&str, Operation> {
 }
 
 fn operand(input: &str) -> IResult<&str, Operand> {
-    alt((
-        map(tag("old"), |_| Operand::Old),
-        map(integer, Operand::Constant),
-    ))(input)
+    context(
+        "operand",
+        alt((
+            map(tag("old"), |_| Operand::Old),
+            map(integer, Operand::Constant),
+        )),
+    )(input)
 }
 
 fn test(input: &str) -> IResult<&str, Test> {
@@ -1,14 +1,14 @@
 use crate::{
-    parsing::{integer, line_separated},
-    Solution,
+    parsing::{all_lines, integer, IResult, ParseError, ParseFinish},
+    Solution, SolutionError,
 };
 use nom::{
     branch::alt,
     character::complete::{char, newline},
-    combinator::{all_consuming, map, opt},
+    combinator::{map, opt},
+    error::context,
     multi::separated_list0,
     sequence::{delimited, separated_pair, terminated},
-    IResult,
 };
 use std::borrow::Borrow;
 use std::cmp::{self, Ord, Ordering, PartialOrd};
@@ -22,22 +22,20 @@ impl Solution for Day13 {
 
     type Input = Vec<ValuePair>;
 
-    fn parse<'a>(
-        &mut self,
-        input: &'a str,
-    ) -> Result<Self::Input, nom::Err<nom::error::Error<&'a str>>> {
-        all_consuming(line_separated(terminated(value_pair, opt(newline))))(input).map(|x| x.1)
+    fn parse(&mut self, input: &str) -> Result<Self::Input, ParseError> {
+        all_lines(terminated(value_pair, opt(newline)))(input).finish_parse(input)
     }
 
-    fn run_part_1(&mut self, data: &Self::Input) -> Self::Part1Result {
-        data.iter()
+    fn run_part_1(&mut self, data: &Self::Input) -> Result<Self::Part1Result, SolutionError> {
+        Ok(data
+            .iter()
             .enumerate()
             .filter(|&(_, p)| p.is_ordered())
             .map(|x| x.0 + 1)
-            .sum()
+            .sum())
     }
 
-    fn run_part_2(&mut self, data: &Self::Input) -> Self::Part2Result {
+    fn run_part_2(&mut self, data: &Self::Input) -> Result<Self::Part2Result, SolutionError> {
         let divider1 = value("[[2]]").unwrap().1;
         let divider2 = value("[[6]]").unwrap().1;
 
@@ -49,7 +47,7 @@ impl Solution for Day13 {
         let index1 = data.binary_search(&&divider1).unwrap();
         let index2 = data.binary_search(&&divider2).unwrap();
 
-        (index1 + 1) * (index2 + 1)
+        Ok((index1 + 1) * (index2 + 1))
     }
 }
 
@@ -108,11 +106,14 @@ fn value_pair(input: &str) -> IResult<&str, ValuePair> {
 }
 
 fn value(input: &str) -> IResult<&str, Value> {
-    alt((
-        map(integer, Value::Integer),
-        map(
-            delimited(char('['), separated_list0(char(','), value), char(']')),
-            Value::List,
-        ),
-    ))(input)
+    context(
+        "packet value",
+        alt((
+            map(integer, Value::Integer),
+            map(
+                delimited(char('['), separated_list0(char(','), value), char(']')),
+                Value::List,
+            ),
+        )),
+    )(input)
 }
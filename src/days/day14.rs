@@ -1,15 +1,11 @@
 use crate::{
-    parsing::{integer, line_separated},
-    Solution,
+    parsing::{all_lines, integer, IResult, ParseError, ParseFinish},
+    Solution, SolutionError,
 };
 use itertools::Itertools;
 use nom::{
-    bytes::complete::tag,
-    character::complete::char,
-    combinator::{all_consuming, map},
-    multi::separated_list1,
+    bytes::complete::tag, character::complete::char, combinator::map, multi::separated_list1,
     sequence::separated_pair,
-    IResult,
 };
 use std::cmp;
 use std::collections::HashMap;
@@ -25,23 +21,20 @@ impl Solution for Day14 {
 
     type Input = Vec<Path>;
 
-    fn parse<'a>(
-        &mut self,
-        input: &'a str,
-    ) -> Result<Self::Input, nom::Err<nom::error::Error<&'a str>>> {
-        all_consuming(line_separated(path))(input).map(|x| x.1)
+    fn parse(&mut self, input: &str) -> Result<Self::Input, ParseError> {
+        all_lines(path)(input).finish_parse(input)
     }
 
-    fn run_part_1(&mut self, data: &Self::Input) -> Self::Part1Result {
+    fn run_part_1(&mut self, data: &Self::Input) -> Result<Self::Part1Result, SolutionError> {
         let lines: Vec<_> = data.iter().flat_map(|p| p.to_lines()).collect();
         let stone_map = StoneMap::new(&lines);
         let mut sand = HashMap::new();
         stone_map.check_if_rests(START, &mut sand);
 
-        sand.values().into_iter().filter(|&&x| x).count()
+        Ok(sand.values().filter(|&&x| x).count())
     }
 
-    fn run_part_2(&mut self, data: &Self::Input) -> Self::Part2Result {
+    fn run_part_2(&mut self, data: &Self::Input) -> Result<Self::Part2Result, SolutionError> {
         let mut lines: Vec<_> = data.iter().flat_map(|p| p.to_lines()).collect();
         lines.push(HorizontalLine {
             x_start: 0,
@@ -52,7 +45,7 @@ impl Solution for Day14 {
         let mut sand = HashMap::new();
         stone_map.check_if_rests(START, &mut sand);
 
-        sand.values().into_iter().filter(|&&x| x).count()
+        Ok(sand.values().filter(|&&x| x).count())
     }
 }
 
@@ -105,7 +98,7 @@ impl StoneMap {
     fn is_stone(&self, coords: Coords) -> bool {
         let lines = self.horizontal_lines.get(&coords.y);
 
-        lines.map_or(false, |lines| {
+        lines.is_some_and(|lines| {
             let closest = ok_or_err(lines.binary_search(&Segment(coords.x, coords.x)));
 
             let first = lines.get(closest);
@@ -116,9 +109,9 @@ impl StoneMap {
             };
             let third = lines.get(closest + 1);
 
-            first.map_or(false, |l| l.contains(coords.x))
-                || second.map_or(false, |l| l.contains(coords.x))
-                || third.map_or(false, |l| l.contains(coords.x))
+            first.is_some_and(|l| l.contains(coords.x))
+                || second.is_some_and(|l| l.contains(coords.x))
+                || third.is_some_and(|l| l.contains(coords.x))
         })
     }
 
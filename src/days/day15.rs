@@ -1,53 +1,159 @@
 use crate::{
-    parsing::{integer, line_separated},
-    Solution,
+    parsing::{all_lines, integer, IResult, ParseError, ParseFinish},
+    CancellationToken, ParamError, Params, Solution, SolutionError,
 };
 use itertools::Itertools;
 use nom::{
     bytes::complete::tag,
-    combinator::{all_consuming, map},
+    combinator::map,
     sequence::{preceded, separated_pair},
-    IResult,
 };
 use std::cmp;
 
-#[derive(Default)]
-pub struct Day15 {}
+pub struct Day15 {
+    target_y: i64,
+    search_limit: i64,
+    cancellation: CancellationToken,
+}
 
-const TARGET_Y: i64 = 2_000_000;
-const Y_THRESHOLD: i64 = 4_000_000;
 const TUNING_CONSTANT: i64 = 4_000_000;
 
+impl Default for Day15 {
+    fn default() -> Self {
+        Self {
+            target_y: 2_000_000,
+            search_limit: 4_000_000,
+            cancellation: CancellationToken::new(),
+        }
+    }
+}
+
 impl Solution for Day15 {
     type Part1Result = usize;
     type Part2Result = i64;
 
     type Input = Vec<Sensor>;
 
-    fn parse<'a>(
-        &mut self,
-        input: &'a str,
-    ) -> Result<Self::Input, nom::Err<nom::error::Error<&'a str>>> {
-        all_consuming(line_separated(sensor))(input).map(|x| x.1)
+    fn configure(&mut self, params: &mut Params) -> Result<(), ParamError> {
+        params.apply("target_y", &mut self.target_y)?;
+        params.apply("search_limit", &mut self.search_limit)
+    }
+
+    fn set_cancellation(&mut self, token: CancellationToken) {
+        self.cancellation = token;
+    }
+
+    fn parse(&mut self, input: &str) -> Result<Self::Input, ParseError> {
+        all_lines(sensor)(input).finish_parse(input)
     }
 
-    fn run_part_1(&mut self, data: &Self::Input) -> Self::Part1Result {
-        let coverage = get_covered_segments(data, TARGET_Y);
-        let without_beacons = remove_beacons(data, TARGET_Y, coverage);
-        without_beacons.into_iter().map(|s| s.len()).sum()
+    fn run_part_1(&mut self, data: &Self::Input) -> Result<Self::Part1Result, SolutionError> {
+        let coverage = get_covered_segments(data, self.target_y);
+        let without_beacons = remove_beacons(data, self.target_y, coverage);
+        Ok(without_beacons.into_iter().map(|s| s.len()).sum())
     }
 
-    fn run_part_2(&mut self, data: &Self::Input) -> Self::Part2Result {
-        for target_y in 0..=Y_THRESHOLD {
+    fn run_part_2(&mut self, data: &Self::Input) -> Result<Self::Part2Result, SolutionError> {
+        for target_y in 0..=self.search_limit {
+            self.cancellation.check()?;
             let coverage = get_covered_segments(data, target_y);
 
             if coverage.len() == 2 {
                 let x = coverage[0].end + 1;
-                return Coords { x, y: target_y }.tuning_frequency();
+                return Ok(Coords { x, y: target_y }.tuning_frequency());
             }
         }
 
-        0
+        Err(SolutionError::no_solution(
+            "every position within the search area is covered by a sensor",
+        ))
+    }
+}
+
+/// Finds the distress beacon without scanning every row: the only uncovered
+/// position must lie just outside the range of the sensors around it, so it
+/// is where two of their boundaries cross, or where one meets the edge of the
+/// search area.
+#[derive(Default)]
+pub struct Day15Geometric {
+    base: Day15,
+}
+
+impl Solution for Day15Geometric {
+    type Part1Result = usize;
+    type Part2Result = i64;
+
+    type Input = Vec<Sensor>;
+
+    fn configure(&mut self, params: &mut Params) -> Result<(), ParamError> {
+        self.base.configure(params)
+    }
+
+    fn set_cancellation(&mut self, token: CancellationToken) {
+        self.base.set_cancellation(token);
+    }
+
+    fn parse(&mut self, input: &str) -> Result<Self::Input, ParseError> {
+        self.base.parse(input)
+    }
+
+    fn run_part_1(&mut self, data: &Self::Input) -> Result<Self::Part1Result, SolutionError> {
+        self.base.run_part_1(data)
+    }
+
+    fn run_part_2(&mut self, data: &Self::Input) -> Result<Self::Part2Result, SolutionError> {
+        let limit = self.base.search_limit;
+        // Boundaries just outside the range of each sensor, as `y - x` for the
+        // ascending sides and `y + x` for the descending ones.
+        let ascending: Vec<_> = data
+            .iter()
+            .flat_map(|s| {
+                let offset = s.location.y - s.location.x;
+                [offset - s.radius() - 1, offset + s.radius() + 1]
+            })
+            .unique()
+            .collect();
+        let descending: Vec<_> = data
+            .iter()
+            .flat_map(|s| {
+                let offset = s.location.y + s.location.x;
+                [offset - s.radius() - 1, offset + s.radius() + 1]
+            })
+            .unique()
+            .collect();
+
+        let crossings = ascending
+            .iter()
+            .cartesian_product(descending.iter())
+            .filter(|&(a, b)| (a + b) % 2 == 0)
+            .map(|(a, b)| Coords {
+                x: (b - a) / 2,
+                y: (a + b) / 2,
+            });
+        let on_edges = ascending
+            .iter()
+            .flat_map(|&a| [(0, a), (limit, limit + a), (-a, 0), (limit - a, limit)])
+            .chain(
+                descending
+                    .iter()
+                    .flat_map(|&b| [(0, b), (limit, b - limit), (b, 0), (b - limit, limit)]),
+            )
+            .chain([(0, 0), (0, limit), (limit, 0), (limit, limit)])
+            .map(|(x, y)| Coords { x, y });
+
+        crossings
+            .chain(on_edges)
+            .find(|c| {
+                (0..=limit).contains(&c.x)
+                    && (0..=limit).contains(&c.y)
+                    && data.iter().all(|s| !s.covers(c))
+            })
+            .map(|c| c.tuning_frequency())
+            .ok_or_else(|| {
+                SolutionError::no_solution(
+                    "every position within the search area is covered by a sensor",
+                )
+            })
     }
 }
 
@@ -119,6 +225,14 @@ impl Segment {
 }
 
 impl Sensor {
+    fn radius(&self) -> i64 {
+        self.location.distance_to(&self.beacon)
+    }
+
+    fn covers(&self, point: &Coords) -> bool {
+        self.location.distance_to(point) <= self.radius()
+    }
+
     fn get_target_segment(&self, target: i64) -> Option<Segment> {
         let distance = self.location.distance_to(&self.beacon);
         let source = self.location.project_on_target_y(target);
@@ -189,10 +303,10 @@ fn remove_points<I1: Iterator<Item = Segment>, I2: Iterator<Item = i64>>(
     for segment in segments {
         let mut remainder = Some(segment);
 
-        while point.map_or(false, |p| segment.start > p) {
+        while point.is_some_and(|p| segment.start > p) {
             point = points.next();
         }
-        while remainder.is_some() && point.map_or(false, |p| segment.end >= p) {
+        while remainder.is_some() && point.is_some_and(|p| segment.end >= p) {
             let (left, right) = remainder.unwrap().split_at(point.unwrap());
             remainder = right;
 
@@ -1,15 +1,15 @@
 use crate::{
-    parsing::{integer, line_separated},
-    Solution,
+    parsing::{all_lines, integer, IResult, ParseError, ParseFinish},
+    CancellationToken, ParamError, Params, Solution, SolutionError,
 };
 use nom::{
     branch::alt,
     bytes::complete::tag,
     character::complete::satisfy,
-    combinator::{all_consuming, map},
+    combinator::map,
+    error::context,
     multi::separated_list1,
     sequence::{preceded, tuple},
-    IResult,
 };
 use petgraph::{
     matrix_graph::{MatrixGraph, NodeIndex},
@@ -20,34 +20,52 @@ use std::hash::Hash;
 use std::{cmp, default::Default};
 use std::{cmp::Ordering, collections::HashMap};
 
-#[derive(Default)]
-pub struct Day16 {}
+pub struct Day16 {
+    part_1_time_limit: usize,
+    part_2_time_limit: usize,
+    cancellation: CancellationToken,
+}
 
-const PART_1_TIME_LIMIT: usize = 30;
-const PART_2_TIME_LIMIT: usize = 26;
 const VALVE_OPEN_COST: usize = 1;
 const TUNNEL_MOVE_COST: usize = 1;
 
+impl Default for Day16 {
+    fn default() -> Self {
+        Self {
+            part_1_time_limit: 30,
+            part_2_time_limit: 26,
+            cancellation: CancellationToken::new(),
+        }
+    }
+}
+
 impl Solution for Day16 {
     type Part1Result = FlowUnit;
     type Part2Result = Self::Part1Result;
 
     type Input = Vec<Valve>;
 
-    fn parse<'a>(
-        &mut self,
-        input: &'a str,
-    ) -> Result<Self::Input, nom::Err<nom::error::Error<&'a str>>> {
-        all_consuming(line_separated(valve))(input).map(|x| x.1)
+    fn configure(&mut self, params: &mut Params) -> Result<(), ParamError> {
+        params.apply("part_1_time_limit", &mut self.part_1_time_limit)?;
+        params.apply("part_2_time_limit", &mut self.part_2_time_limit)
+    }
+
+    fn set_cancellation(&mut self, token: CancellationToken) {
+        self.cancellation = token;
+    }
+
+    fn parse(&mut self, input: &str) -> Result<Self::Input, ParseError> {
+        all_lines(valve)(input).finish_parse(input)
     }
 
-    fn run_part_1(&mut self, data: &Self::Input) -> Self::Part1Result {
+    fn run_part_1(&mut self, data: &Self::Input) -> Result<Self::Part1Result, SolutionError> {
+        let time_limit = self.part_1_time_limit;
         let (start, mut graph) = build_valve_graph(data);
         find_shortest_paths(&mut graph);
         remove_useless_nodes(&mut graph, start);
 
         let mut results: Vec<HashMap<State, FlowUnit>> = std::iter::repeat_with(HashMap::new)
-            .take(PART_1_TIME_LIMIT + 1)
+            .take(time_limit + 1)
             .collect();
         let mut initial_state = State::default();
         initial_state.move_to(start);
@@ -55,16 +73,16 @@ impl Solution for Day16 {
         for target in graph.node_identifiers() {
             let dist = graph[(start, target)];
 
-            if dist + VALVE_OPEN_COST <= PART_1_TIME_LIMIT {
+            if dist + VALVE_OPEN_COST <= time_limit {
                 let mut new_state = initial_state;
                 new_state.move_to(target);
-                results[PART_1_TIME_LIMIT - dist].insert(new_state, 0);
+                results[time_limit - dist].insert(new_state, 0);
             }
         }
 
         let mut result = 0;
 
-        for time in (0..PART_1_TIME_LIMIT).rev() {
+        for time in (0..time_limit).rev() {
             let mut iterable = HashMap::new();
             std::mem::swap(&mut results[time], &mut iterable);
             for (state, total_flow) in iterable {
@@ -93,20 +111,21 @@ impl Solution for Day16 {
             }
         }
 
-        result
+        Ok(result)
     }
 
-    fn run_part_2(&mut self, data: &Self::Input) -> Self::Part2Result {
+    fn run_part_2(&mut self, data: &Self::Input) -> Result<Self::Part2Result, SolutionError> {
+        let time_limit = self.part_2_time_limit;
         let (start, mut graph) = build_valve_graph(data);
         find_shortest_paths(&mut graph);
         remove_useless_nodes(&mut graph, start);
 
         let mut results: Vec<Vec<HashMap<DoubleState, FlowUnit>>> = std::iter::repeat_with(|| {
             std::iter::repeat_with(HashMap::new)
-                .take(PART_2_TIME_LIMIT + 1)
+                .take(time_limit + 1)
                 .collect()
         })
-        .take(PART_2_TIME_LIMIT + 1)
+        .take(time_limit + 1)
         .collect();
         let mut initial_state = DoubleState::default();
         initial_state.move_1_to(start);
@@ -117,25 +136,24 @@ impl Solution for Day16 {
                 let dist_1 = graph[(start, target_1)];
                 let dist_2 = graph[(start, target_2)];
 
-                if dist_1 + VALVE_OPEN_COST <= PART_2_TIME_LIMIT
-                    && dist_2 + VALVE_OPEN_COST <= PART_2_TIME_LIMIT
+                if dist_1 + VALVE_OPEN_COST <= time_limit && dist_2 + VALVE_OPEN_COST <= time_limit
                 {
                     let mut new_state = initial_state;
                     new_state.move_1_to(target_1);
                     new_state.move_2_to(target_2);
-                    results[PART_2_TIME_LIMIT - dist_1][PART_2_TIME_LIMIT - dist_2]
-                        .insert(new_state, 0);
+                    results[time_limit - dist_1][time_limit - dist_2].insert(new_state, 0);
                 }
             }
         }
 
         let mut result = 0;
 
-        for time_1 in (0..PART_2_TIME_LIMIT).rev() {
-            for time_2 in (0..PART_2_TIME_LIMIT).rev() {
+        for time_1 in (0..time_limit).rev() {
+            for time_2 in (0..time_limit).rev() {
                 let mut iterable = HashMap::new();
                 std::mem::swap(&mut results[time_1][time_2], &mut iterable);
                 for (state, total_flow) in iterable {
+                    self.cancellation.check()?;
                     let valve_1 = state.current_valve_1;
                     let valve_2 = state.current_valve_2;
                     let gain_1 = if time_1 >= VALVE_OPEN_COST {
@@ -207,7 +225,7 @@ impl Solution for Day16 {
             }
         }
 
-        result
+        Ok(result)
     }
 }
 
@@ -437,11 +455,14 @@ fn valve(input: &str) -> IResult<&str, Valve> {
 }
 
 fn valve_id(input: &str) -> IResult<&str, ValveId> {
-    map(
-        tuple((
-            satisfy(|c| c.is_ascii_uppercase()),
-            satisfy(|c| c.is_ascii_uppercase()),
-        )),
-        |(a, b)| ValveId(a, b),
+    context(
+        "valve id",
+        map(
+            tuple((
+                satisfy(|c| c.is_ascii_uppercase()),
+                satisfy(|c| c.is_ascii_uppercase()),
+            )),
+            |(a, b)| ValveId(a, b),
+        ),
     )(input)
 }
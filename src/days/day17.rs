@@ -1,15 +1,16 @@
-use crate::Solution;
+use crate::{
+    parsing::{IResult, ParseError, ParseFinish},
+    ParamError, Params, Solution, SolutionError,
+};
 use nom::{
     branch::alt,
     character::complete::char,
     combinator::{all_consuming, map},
+    error::context,
     multi::many1,
-    IResult,
 };
 use std::{cmp, collections::HashSet};
 
-const PART_1_PIECE_LIMIT: usize = 2022;
-const PART_2_PIECE_LIMIT: usize = 1_000_000_000_000;
 const PIECE_SEQUENCE: [PieceType; 5] = [
     PieceType::LongHorizontal,
     PieceType::Plus,
@@ -18,8 +19,19 @@ const PIECE_SEQUENCE: [PieceType; 5] = [
     PieceType::Square,
 ];
 
-#[derive(Default)]
-pub struct Day17 {}
+pub struct Day17 {
+    part_1_pieces: usize,
+    part_2_pieces: usize,
+}
+
+impl Default for Day17 {
+    fn default() -> Self {
+        Self {
+            part_1_pieces: 2022,
+            part_2_pieces: 1_000_000_000_000,
+        }
+    }
+}
 
 impl Solution for Day17 {
     type Part1Result = usize;
@@ -27,28 +39,20 @@ impl Solution for Day17 {
 
     type Input = Vec<Move>;
 
-    fn parse<'a>(
-        &mut self,
-        input: &'a str,
-    ) -> Result<Self::Input, nom::Err<nom::error::Error<&'a str>>> {
-        all_consuming(many1(mov))(input).map(|x| x.1)
+    fn configure(&mut self, params: &mut Params) -> Result<(), ParamError> {
+        params.apply("part_1_pieces", &mut self.part_1_pieces)?;
+        params.apply("part_2_pieces", &mut self.part_2_pieces)
     }
 
-    fn run_part_1(&mut self, data: &Self::Input) -> Self::Part1Result {
-        let piece_types = std::iter::repeat(PIECE_SEQUENCE)
-            .flatten()
-            .take(PART_1_PIECE_LIMIT);
-        let mut moves = std::iter::repeat(data.iter().copied().enumerate()).flatten();
-        let mut playground = Playground::new();
-
-        for piece_type in piece_types {
-            dispatch_piece(&mut playground, &mut moves, piece_type);
-        }
+    fn parse(&mut self, input: &str) -> Result<Self::Input, ParseError> {
+        all_consuming(many1(mov))(input).finish_parse(input)
+    }
 
-        playground.watermark()
+    fn run_part_1(&mut self, data: &Self::Input) -> Result<Self::Part1Result, SolutionError> {
+        Ok(height_after(data, self.part_1_pieces))
     }
 
-    fn run_part_2(&mut self, data: &Self::Input) -> Self::Part2Result {
+    fn run_part_2(&mut self, data: &Self::Input) -> Result<Self::Part2Result, SolutionError> {
         let mut piece_types =
             std::iter::repeat(PIECE_SEQUENCE.iter().copied().enumerate()).flatten();
         let mut moves = std::iter::repeat(data.iter().copied().enumerate()).flatten();
@@ -80,8 +84,13 @@ impl Solution for Day17 {
             }
         }
 
+        // Finding the cycle already went past the pieces asked for.
+        if self.part_2_pieces <= pieces_count {
+            return Ok(height_after(data, self.part_2_pieces));
+        }
+
         let cycle_factor = playground.watermark() - constant_factor;
-        let remainder = (PART_2_PIECE_LIMIT - pieces_count) % cycle_length;
+        let remainder = (self.part_2_pieces - pieces_count) % cycle_length;
 
         for _ in 0..remainder {
             let (_, piece_type) = piece_types.next().unwrap();
@@ -89,13 +98,26 @@ impl Solution for Day17 {
             pieces_count += 1;
         }
 
-        let remaining_cycles = (PART_2_PIECE_LIMIT - pieces_count) / cycle_length;
+        let remaining_cycles = (self.part_2_pieces - pieces_count) / cycle_length;
         let remaining_factor = remaining_cycles * cycle_factor;
 
-        playground.watermark() + remaining_factor
+        Ok(playground.watermark() + remaining_factor)
     }
 }
 
+/// Drops `pieces` pieces one by one and returns the height of the tower.
+fn height_after(data: &[Move], pieces: usize) -> usize {
+    let piece_types = std::iter::repeat(PIECE_SEQUENCE).flatten().take(pieces);
+    let mut moves = std::iter::repeat(data.iter().copied().enumerate()).flatten();
+    let mut playground = Playground::new();
+
+    for piece_type in piece_types {
+        dispatch_piece(&mut playground, &mut moves, piece_type);
+    }
+
+    playground.watermark()
+}
+
 fn dispatch_piece<I: Iterator<Item = (usize, Move)>>(
     playground: &mut Playground,
     moves: &mut I,
@@ -444,8 +466,11 @@ impl Piece for Square {
 }
 
 fn mov(input: &str) -> IResult<&str, Move> {
-    alt((
-        map(char('<'), |_| Move::Left),
-        map(char('>'), |_| Move::Right),
-    ))(input)
+    context(
+        "jet direction",
+        alt((
+            map(char('<'), |_| Move::Left),
+            map(char('>'), |_| Move::Right),
+        )),
+    )(input)
 }
@@ -1,14 +1,9 @@
 use crate::{
-    parsing::{integer, line_separated},
-    Solution,
+    parsing::{all_lines, integer, IResult, ParseError, ParseFinish},
+    Solution, SolutionError,
 };
 use itertools::Itertools;
-use nom::{
-    character::complete::char,
-    combinator::{all_consuming, map},
-    sequence::separated_pair,
-    IResult,
-};
+use nom::{character::complete::char, combinator::map, sequence::separated_pair};
 use std::{cmp, collections::HashMap};
 
 #[derive(Default)]
@@ -20,14 +15,11 @@ impl Solution for Day18 {
 
     type Input = Vec<Coords>;
 
-    fn parse<'a>(
-        &mut self,
-        input: &'a str,
-    ) -> Result<Self::Input, nom::Err<nom::error::Error<&'a str>>> {
-        all_consuming(line_separated(cube))(input).map(|x| x.1)
+    fn parse(&mut self, input: &str) -> Result<Self::Input, ParseError> {
+        all_lines(cube)(input).finish_parse(input)
     }
 
-    fn run_part_1(&mut self, data: &Self::Input) -> Self::Part1Result {
+    fn run_part_1(&mut self, data: &Self::Input) -> Result<Self::Part1Result, SolutionError> {
         let group_by_xy = group_dimension(data, |c| ((c.x, c.y), c.z));
         let group_by_xz = group_dimension(data, |c| ((c.x, c.z), c.y));
         let group_by_yz = group_dimension(data, |c| ((c.y, c.z), c.x));
@@ -45,10 +37,10 @@ impl Solution for Day18 {
             .map(|line| concat_lines(line.into_iter()).len() * 2)
             .sum();
 
-        result_xy + result_xz + result_yz
+        Ok(result_xy + result_xz + result_yz)
     }
 
-    fn run_part_2(&mut self, data: &Self::Input) -> Self::Part2Result {
+    fn run_part_2(&mut self, data: &Self::Input) -> Result<Self::Part2Result, SolutionError> {
         let max_coords = data
             .iter()
             .fold(Coords::default(), |a, c| Coords {
@@ -61,7 +53,8 @@ impl Solution for Day18 {
 
         mark_exterior(max_coords, &mut map, &max_coords);
 
-        data.iter()
+        Ok(data
+            .iter()
             .map(|c| {
                 c.neighbors()
                     .into_iter()
@@ -73,7 +66,7 @@ impl Solution for Day18 {
                     })
                     .count()
             })
-            .sum()
+            .sum())
     }
 }
 
@@ -1,23 +1,24 @@
 use crate::{
-    parsing::{integer, line_separated},
-    Solution,
+    parsing::{all_lines, integer, IResult, ParseError, ParseFinish},
+    CancellationToken, Solution, SolutionError,
 };
 use nom::{
     branch::alt,
     bytes::complete::tag,
-    combinator::{all_consuming, map, opt},
+    combinator::{map, opt},
     sequence::{delimited, preceded, terminated, tuple},
-    IResult,
 };
-use ordered_float::NotNan;
 use rayon::prelude::*;
 use std::{
     cmp::{self, Ordering},
     collections::HashSet,
 };
+use tracing::{debug, debug_span};
 
 #[derive(Default)]
-pub struct Day19 {}
+pub struct Day19 {
+    cancellation: CancellationToken,
+}
 
 const ALL_BUILD_ACTIONS: [Action; 4] = [
     Action::BuildGeodeRobot,
@@ -32,31 +33,46 @@ impl Solution for Day19 {
 
     type Input = Vec<Blueprint>;
 
-    fn parse<'a>(
-        &mut self,
-        input: &'a str,
-    ) -> Result<Self::Input, nom::Err<nom::error::Error<&'a str>>> {
-        all_consuming(line_separated(blueprint))(input).map(|x| x.1)
+    fn set_cancellation(&mut self, token: CancellationToken) {
+        self.cancellation = token;
+    }
+
+    fn parse(&mut self, input: &str) -> Result<Self::Input, ParseError> {
+        all_lines(blueprint)(input).finish_parse(input)
     }
 
-    fn run_part_1(&mut self, data: &Self::Input) -> Self::Part1Result {
-        data.par_iter().map(|b| b.id * run_blueprint(b, 24)).sum()
+    fn run_part_1(&mut self, data: &Self::Input) -> Result<Self::Part1Result, SolutionError> {
+        data.par_iter()
+            .map(|b| Ok(b.id * run_blueprint(b, 24, &self.cancellation)?))
+            .sum()
     }
 
-    fn run_part_2(&mut self, data: &Self::Input) -> Self::Part2Result {
+    fn run_part_2(&mut self, data: &Self::Input) -> Result<Self::Part2Result, SolutionError> {
         data.par_iter()
             .take(3)
-            .map(|b| run_blueprint(b, 32))
+            .map(|b| run_blueprint(b, 32, &self.cancellation))
             .product()
     }
 }
 
-fn run_blueprint(blueprint: &Blueprint, time_limit: usize) -> usize {
+fn run_blueprint(
+    blueprint: &Blueprint,
+    time_limit: usize,
+    cancellation: &CancellationToken,
+) -> Result<usize, SolutionError> {
+    let _span = debug_span!("blueprint", id = blueprint.id).entered();
     let start = State::default();
-    exhaustive_search(blueprint, start, time_limit)
+    let geodes = exhaustive_search(blueprint, start, time_limit, cancellation)?;
+    debug!(geodes, "search finished");
+    Ok(geodes)
 }
 
-fn exhaustive_search(blueprint: &Blueprint, start: State, time_limit: usize) -> usize {
+fn exhaustive_search(
+    blueprint: &Blueprint,
+    start: State,
+    time_limit: usize,
+    cancellation: &CancellationToken,
+) -> Result<usize, SolutionError> {
     let mut active = Vec::new();
     let mut max_geodes = 0;
     let mut visited = HashSet::new();
@@ -64,6 +80,8 @@ fn exhaustive_search(blueprint: &Blueprint, start: State, time_limit: usize) ->
     active.push(start);
 
     while let Some(state) = active.pop() {
+        cancellation.check()?;
+
         if !visited.insert(state) || state.estimate_max_geodes(time_limit) <= max_geodes {
             continue;
         }
@@ -88,24 +106,7 @@ fn exhaustive_search(blueprint: &Blueprint, start: State, time_limit: usize) ->
         }
     }
 
-    max_geodes
-}
-
-type Float = NotNan<f32>;
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-struct GeodeEst(Float);
-
-impl PartialOrd for GeodeEst {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        self.0.partial_cmp(&other.0).map(|x| x.reverse())
-    }
-}
-
-impl Ord for GeodeEst {
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.0.cmp(&other.0).reverse()
-    }
+    Ok(max_geodes)
 }
 
 #[derive(Debug)]
@@ -234,7 +235,7 @@ impl State {
 
     fn estimate_max_geodes(&self, time_limit: usize) -> usize {
         let time = time_limit - self.time_elapsed;
-        self.resources.geode + time * self.robots.geode + (time * (time - 1)) / 2
+        self.resources.geode + time * self.robots.geode + (time * time.saturating_sub(1)) / 2
     }
 }
 
@@ -249,6 +250,7 @@ impl Default for Robots {
     }
 }
 
+#[allow(clippy::non_canonical_partial_ord_impl)]
 impl PartialOrd for Robots {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         match (
@@ -277,6 +279,7 @@ impl Ord for Robots {
     }
 }
 
+#[allow(clippy::non_canonical_partial_ord_impl)]
 impl PartialOrd for Resources {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         match (
@@ -1,11 +1,11 @@
-use crate::parsing::line_separated;
-use crate::Solution;
+use crate::parsing::{all_lines, IResult, ParseError, ParseFinish};
+use crate::{Solution, SolutionError};
 use nom::{
     branch::alt,
     character::complete::{char as nom_char, space1},
-    combinator::{all_consuming, map},
+    combinator::map,
+    error::context,
     sequence::separated_pair,
-    IResult,
 };
 
 #[derive(Default)]
@@ -17,19 +17,16 @@ impl Solution for Day2 {
 
     type Input = Vec<Round>;
 
-    fn parse<'a>(
-        &mut self,
-        input: &'a str,
-    ) -> Result<Self::Input, nom::Err<nom::error::Error<&'a str>>> {
-        all_consuming(line_separated(round))(input).map(|x| x.1)
+    fn parse(&mut self, input: &str) -> Result<Self::Input, ParseError> {
+        all_lines(round)(input).finish_parse(input)
     }
 
-    fn run_part_1(&mut self, data: &Self::Input) -> Self::Part1Result {
-        data.iter().map(|x| x.score_part_1()).sum()
+    fn run_part_1(&mut self, data: &Self::Input) -> Result<Self::Part1Result, SolutionError> {
+        Ok(data.iter().map(|x| x.score_part_1()).sum())
     }
 
-    fn run_part_2(&mut self, data: &Self::Input) -> Self::Part2Result {
-        data.iter().map(|x| x.score_part_2()).sum()
+    fn run_part_2(&mut self, data: &Self::Input) -> Result<Self::Part2Result, SolutionError> {
+        Ok(data.iter().map(|x| x.score_part_2()).sum())
     }
 }
 
@@ -161,17 +158,23 @@ fn round(input: &str) -> IResult<&str, Round> {
 }
 
 fn opponent_shape(input: &str) -> IResult<&str, Shape> {
-    alt((
-        map(nom_char('A'), |_| Shape::Rock),
-        map(nom_char('B'), |_| Shape::Paper),
-        map(nom_char('C'), |_| Shape::Scissors),
-    ))(input)
+    context(
+        "opponent shape",
+        alt((
+            map(nom_char('A'), |_| Shape::Rock),
+            map(nom_char('B'), |_| Shape::Paper),
+            map(nom_char('C'), |_| Shape::Scissors),
+        )),
+    )(input)
 }
 
 fn shape_or_outcome(input: &str) -> IResult<&str, ShapeOrOutcome> {
-    alt((
-        map(nom_char('X'), |_| ShapeOrOutcome::RockOrLoss),
-        map(nom_char('Y'), |_| ShapeOrOutcome::PaperOrDraw),
-        map(nom_char('Z'), |_| ShapeOrOutcome::ScissorsOrWin),
-    ))(input)
+    context(
+        "shape or outcome",
+        alt((
+            map(nom_char('X'), |_| ShapeOrOutcome::RockOrLoss),
+            map(nom_char('Y'), |_| ShapeOrOutcome::PaperOrDraw),
+            map(nom_char('Z'), |_| ShapeOrOutcome::ScissorsOrWin),
+        )),
+    )(input)
 }
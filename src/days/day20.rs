@@ -1,8 +1,7 @@
 use crate::{
-    parsing::{integer, line_separated},
-    Solution,
+    parsing::{all_lines, integer, ParseError, ParseFinish},
+    Solution, SolutionError,
 };
-use nom::combinator::all_consuming;
 use std::{
     cell::{Ref, RefCell, RefMut},
     cmp,
@@ -23,25 +22,33 @@ impl Solution for Day20 {
 
     type Input = Vec<Int>;
 
-    fn parse<'a>(
-        &mut self,
-        input: &'a str,
-    ) -> Result<Self::Input, nom::Err<nom::error::Error<&'a str>>> {
-        all_consuming(line_separated(integer))(input).map(|x| x.1)
+    fn parse(&mut self, input: &str) -> Result<Self::Input, ParseError> {
+        all_lines(integer)(input).finish_parse(input)
     }
 
-    fn run_part_1(&mut self, data: &Self::Input) -> Self::Part1Result {
+    fn run_part_1(&mut self, data: &Self::Input) -> Result<Self::Part1Result, SolutionError> {
         mix(data, 1, 1)
     }
 
-    fn run_part_2(&mut self, data: &Self::Input) -> Self::Part2Result {
+    fn run_part_2(&mut self, data: &Self::Input) -> Result<Self::Part2Result, SolutionError> {
         mix(data, MAGIC_CONSTANT, REPETITIONS)
     }
 }
 
-fn mix(numbers: &[Int], multiplier: Int, times: usize) -> Int {
+fn mix(numbers: &[Int], multiplier: Int, times: usize) -> Result<Int, SolutionError> {
+    // A number moves around the list without itself, so a single number has
+    // nowhere to go.
+    if numbers.len() < 2 {
+        return Err(SolutionError::invalid_input(
+            "mixing needs at least 2 numbers",
+        ));
+    }
+
     let modulo = numbers.len() as Int - 1;
-    let zero_initial_idx = numbers.iter().position(|&x| x == 0).expect("no 0 in input");
+    let zero_initial_idx = numbers
+        .iter()
+        .position(|&x| x == 0)
+        .ok_or_else(|| SolutionError::invalid_input("there is no 0 in the input"))?;
     let mut tree = None;
     let mut node_map = Vec::with_capacity(numbers.len());
     for (i, x) in numbers.iter().map(|x| x * multiplier).enumerate() {
@@ -70,7 +77,7 @@ fn mix(numbers: &[Int], multiplier: Int, times: usize) -> Int {
         result += final_list[actual_idx];
     }
 
-    result
+    Ok(result)
 }
 
 fn insert<T>(root: &mut Option<NodeRef<T>>, element: T, idx: usize) -> NodeRef<T> {
@@ -456,36 +463,36 @@ impl<T> NodeRef<T> {
         self.right().as_ref().map_or(0, |n| n.height())
     }
 
-    fn left(&self) -> Ref<Option<NodeRef<T>>> {
+    fn left(&self) -> Ref<'_, Option<NodeRef<T>>> {
         Ref::map(self.0.borrow(), |x| &x.left)
     }
 
-    fn right(&self) -> Ref<Option<NodeRef<T>>> {
+    fn right(&self) -> Ref<'_, Option<NodeRef<T>>> {
         Ref::map(self.0.borrow(), |x| &x.right)
     }
 
-    fn parent(&self) -> Ref<Option<NodeRef<T>>> {
+    fn parent(&self) -> Ref<'_, Option<NodeRef<T>>> {
         Ref::map(self.0.borrow(), |x| &x.parent)
     }
 
-    fn left_mut(&self) -> RefMut<Option<NodeRef<T>>> {
+    fn left_mut(&self) -> RefMut<'_, Option<NodeRef<T>>> {
         RefMut::map(self.0.borrow_mut(), |x| &mut x.left)
     }
 
-    fn right_mut(&self) -> RefMut<Option<NodeRef<T>>> {
+    fn right_mut(&self) -> RefMut<'_, Option<NodeRef<T>>> {
         RefMut::map(self.0.borrow_mut(), |x| &mut x.right)
     }
 
-    fn parent_mut(&self) -> RefMut<Option<NodeRef<T>>> {
+    fn parent_mut(&self) -> RefMut<'_, Option<NodeRef<T>>> {
         RefMut::map(self.0.borrow_mut(), |x| &mut x.parent)
     }
 
     fn is_left(&self, node: &NodeRef<T>) -> bool {
-        self.left().as_ref().map_or(false, |n| n.eq(node))
+        self.left().as_ref().is_some_and(|n| n.eq(node))
     }
 
     fn is_right(&self, node: &NodeRef<T>) -> bool {
-        self.right().as_ref().map_or(false, |n| n.eq(node))
+        self.right().as_ref().is_some_and(|n| n.eq(node))
     }
 
     fn height(&self) -> usize {
@@ -1,17 +1,18 @@
 use std::{collections::HashMap, fmt::Display};
 
 use crate::{
-    parsing::{integer, line_separated},
-    Solution,
+    parsing::{all_lines, integer, IResult, ParseError, ParseFinish},
+    Answer, Solution, SolutionError,
 };
 use nom::{
     branch::alt,
     bytes::complete::tag,
     character::complete::alpha0,
-    combinator::{all_consuming, map},
+    combinator::map,
+    error::context,
     sequence::{separated_pair, tuple},
-    IResult,
 };
+use tracing::debug;
 
 #[derive(Default)]
 pub struct Day21 {}
@@ -22,33 +23,32 @@ impl Solution for Day21 {
 
     type Input = Vec<Monkey>;
 
-    fn parse<'a>(
-        &mut self,
-        input: &'a str,
-    ) -> Result<Self::Input, nom::Err<nom::error::Error<&'a str>>> {
-        all_consuming(line_separated(monkey))(input).map(|x| x.1)
+    fn parse(&mut self, input: &str) -> Result<Self::Input, ParseError> {
+        all_lines(monkey)(input).finish_parse(input)
     }
 
-    fn run_part_1(&mut self, data: &Self::Input) -> Self::Part1Result {
+    fn run_part_1(&mut self, data: &Self::Input) -> Result<Self::Part1Result, SolutionError> {
         let monkeys = data.iter().map(|m| (&m.id as &str, &m.job)).collect();
         let mut cache = HashMap::new();
 
         build_tree("root", &monkeys, &mut cache)
     }
 
-    fn run_part_2(&mut self, data: &Self::Input) -> Self::Part2Result {
+    fn run_part_2(&mut self, data: &Self::Input) -> Result<Self::Part2Result, SolutionError> {
         let mut monkeys: HashMap<&str, &Job> =
             data.iter().map(|m| (&m.id as &str, &m.job)).collect();
-        let root_job = monkeys.get_mut("root").expect("no root job");
+        let root_job = monkeys
+            .get_mut("root")
+            .ok_or_else(|| SolutionError::invalid_input("there is no root monkey"))?;
         let fixed_root_job = root_fixup(root_job);
         *root_job = &fixed_root_job;
 
         let mut cache = HashMap::new();
         cache.insert("humn", NodeRef(Box::new(Node::Variable)));
 
-        let tree = build_tree("root", &monkeys, &mut cache);
-        println!("initial: {tree}");
-        let unknown = equate_with_zero(tree);
+        let tree = build_tree("root", &monkeys, &mut cache)?;
+        debug!("initial equation: {tree}");
+        let unknown = equate_with_zero(tree)?;
 
         solve_equation(unknown, 0)
     }
@@ -58,15 +58,18 @@ fn build_tree<'a>(
     id: &'a str,
     jobs: &'a HashMap<&str, &Job>,
     cache: &mut HashMap<&'a str, NodeRef>,
-) -> NodeRef {
+) -> Result<NodeRef, SolutionError> {
     if let Some(val) = cache.get(id) {
-        val.clone()
+        Ok(val.clone())
     } else {
-        let node = match jobs[id] {
+        let job = jobs
+            .get(id)
+            .ok_or_else(|| SolutionError::invalid_input(format!("there is no monkey {id}")))?;
+        let node = match job {
             Job::Constant(x) => Node::Value(*x),
             Job::Operation(m1, op, m2) => {
-                let left = build_tree(m1, jobs, cache);
-                let right = build_tree(m2, jobs, cache);
+                let left = build_tree(m1, jobs, cache)?;
+                let right = build_tree(m2, jobs, cache)?;
 
                 match (left.try_value(), right.try_value()) {
                     (Some(v1), Some(v2)) if *op != Operation::Eq => {
@@ -86,7 +89,7 @@ fn build_tree<'a>(
 
         let result = NodeRef(Box::new(node));
         cache.insert(id, result.clone());
-        result
+        Ok(result)
     }
 }
 
@@ -97,27 +100,33 @@ fn root_fixup(job: &Job) -> Job {
     }
 }
 
-fn equate_with_zero(node: NodeRef) -> NodeRef {
+fn equate_with_zero(node: NodeRef) -> Result<NodeRef, SolutionError> {
     match *node.0 {
         Node::Operation(l, Operation::Eq, r) => {
             let new_equation = Node::Operation(l, Operation::Sub, r);
-            NodeRef(Box::new(new_equation))
+            Ok(NodeRef(Box::new(new_equation)))
         }
-        _ => panic!("not an equation"),
+        _ => Err(SolutionError::invalid_input(
+            "the root monkey does not compare two numbers",
+        )),
     }
 }
 
-fn solve_equation(equation: NodeRef, solution: Int) -> Int {
+fn solve_equation(equation: NodeRef, solution: Int) -> Result<Int, SolutionError> {
     match *equation.0 {
-        Node::Value(_) => panic!("no variable"),
-        Node::Variable => solution,
+        Node::Value(_) => Err(SolutionError::no_solution(
+            "the equation does not depend on humn",
+        )),
+        Node::Variable => Ok(solution),
         Node::Operation(l, op, r) => {
             let (rem, val, reverse) = if let Some(val) = l.try_value() {
                 (r, val, true)
             } else if let Some(val) = r.try_value() {
                 (l, val, false)
             } else {
-                panic!("not a simple equation")
+                return Err(SolutionError::no_solution(
+                    "humn appears on both sides of an operation, which is not a simple equation",
+                ));
             };
 
             let solution = match op {
@@ -179,6 +188,17 @@ impl NodeRef {
     }
 }
 
+/// A tree that was reduced to a value is the answer, any other is shown as the
+/// equation it is left with.
+impl From<NodeRef> for Answer {
+    fn from(value: NodeRef) -> Self {
+        match value.try_value() {
+            Some(value) => Answer::Integer(value.into()),
+            None => Answer::Text(value.to_string()),
+        }
+    }
+}
+
 impl Display for NodeRef {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self.0.as_ref() {
@@ -209,19 +229,25 @@ fn monkey(input: &str) -> IResult<&str, Monkey> {
 }
 
 fn job(input: &str) -> IResult<&str, Job> {
-    alt((
-        map(integer, Job::Constant),
-        map(tuple((alpha0, operation, alpha0)), |(id1, op, id2)| {
-            Job::Operation(id1.to_owned(), op, id2.to_owned())
-        }),
-    ))(input)
+    context(
+        "job",
+        alt((
+            map(integer, Job::Constant),
+            map(tuple((alpha0, operation, alpha0)), |(id1, op, id2)| {
+                Job::Operation(id1.to_owned(), op, id2.to_owned())
+            }),
+        )),
+    )(input)
 }
 
 fn operation(input: &str) -> IResult<&str, Operation> {
-    alt((
-        map(tag(" + "), |_| Operation::Add),
-        map(tag(" - "), |_| Operation::Sub),
-        map(tag(" * "), |_| Operation::Mul),
-        map(tag(" / "), |_| Operation::Div),
-    ))(input)
+    context(
+        "operator",
+        alt((
+            map(tag(" + "), |_| Operation::Add),
+            map(tag(" - "), |_| Operation::Sub),
+            map(tag(" * "), |_| Operation::Mul),
+            map(tag(" / "), |_| Operation::Div),
+        )),
+    )(input)
 }
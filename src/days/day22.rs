@@ -1,26 +1,456 @@
-use crate::Solution;
+use crate::{
+    parsing::{integer, line_separated, IResult, ParseError, ParseFinish},
+    Solution, SolutionError,
+};
+use nom::{
+    branch::alt,
+    character::complete::{char, multispace0},
+    combinator::{all_consuming, map},
+    error::context,
+    multi::many1,
+    sequence::{terminated, tuple},
+};
+use std::{
+    collections::{HashMap, VecDeque},
+    ops::{Add, Mul, Neg, Sub},
+};
 
 #[derive(Default)]
 pub struct Day22 {}
 
 impl Solution for Day22 {
-    type Part1Result = String;
+    type Part1Result = usize;
     type Part2Result = Self::Part1Result;
 
-    type Input = ();
+    type Input = Notes;
 
-    fn parse<'a>(
-        &mut self,
-        _input: &'a str,
-    ) -> Result<Self::Input, nom::Err<nom::error::Error<&'a str>>> {
-        todo!()
+    fn parse(&mut self, input: &str) -> Result<Self::Input, ParseError> {
+        all_consuming(terminated(
+            map(tuple((board, path)), |(board, path)| Notes { board, path }),
+            multispace0,
+        ))(input)
+        .finish_parse(input)
     }
 
-    fn run_part_1(&mut self, _data: &Self::Input) -> Self::Part1Result {
-        todo!()
+    fn run_part_1(&mut self, data: &Self::Input) -> Result<Self::Part1Result, SolutionError> {
+        walk(data, |position, facing| {
+            Ok((data.board.wrap_around(position, facing), facing))
+        })
     }
 
-    fn run_part_2(&mut self, _data: &Self::Input) -> Self::Part2Result {
-        todo!()
+    fn run_part_2(&mut self, data: &Self::Input) -> Result<Self::Part2Result, SolutionError> {
+        let cube = Cube::fold(&data.board)?;
+        walk(data, |position, facing| {
+            Ok(cube.cross_edge(position, facing))
+        })
     }
 }
+
+/// Follows the path from the leftmost open tile of the top row, and returns
+/// the password of the final position. `wrap` gives the position and facing
+/// after stepping off the board.
+fn walk<W>(notes: &Notes, wrap: W) -> Result<usize, SolutionError>
+where
+    W: Fn(Position, Facing) -> Result<(Position, Facing), SolutionError>,
+{
+    let board = &notes.board;
+    let x = board.rows[0]
+        .iter()
+        .position(|&x| x == Tile::Open)
+        .ok_or_else(|| SolutionError::invalid_input("the top row has no open tile"))?;
+    let mut position = Position { x, y: 0 };
+    let mut facing = Facing::Right;
+
+    for instruction in notes.path.iter() {
+        match *instruction {
+            Instruction::Turn(turn) => facing = facing.turn(turn),
+            Instruction::Forward(steps) => {
+                for _ in 0..steps {
+                    let (next, next_facing) = match position.step(facing) {
+                        next if board[next] != Tile::Void => (next, facing),
+                        _ => wrap(position, facing)?,
+                    };
+
+                    if board[next] == Tile::Wall {
+                        break;
+                    }
+
+                    position = next;
+                    facing = next_facing;
+                }
+            }
+        }
+    }
+
+    Ok(1000 * (position.y + 1) + 4 * (position.x + 1) + facing as usize)
+}
+
+#[derive(Debug)]
+pub struct Notes {
+    board: Board,
+    path: Vec<Instruction>,
+}
+
+/// Rows of the map, which only cover its tiles up to the last one that is
+/// not void.
+#[derive(Debug)]
+struct Board {
+    rows: Vec<Vec<Tile>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tile {
+    Void,
+    Open,
+    Wall,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Instruction {
+    Forward(usize),
+    Turn(Turn),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Turn {
+    Left,
+    Right,
+}
+
+/// Facings in the order of their value in the password.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Facing {
+    Right,
+    Down,
+    Left,
+    Up,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Position {
+    x: usize,
+    y: usize,
+}
+
+/// The board folded into a cube, with a face for every square of the net.
+struct Cube {
+    size: usize,
+    faces: Vec<Face>,
+}
+
+/// A face of the cube, placed in space by the directions its right and down
+/// point to, and the normal pointing out of the cube.
+#[derive(Debug, Clone, Copy)]
+struct Face {
+    origin: Position,
+    right: Vector,
+    down: Vector,
+    normal: Vector,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Vector {
+    x: i64,
+    y: i64,
+    z: i64,
+}
+
+impl Board {
+    fn width(&self) -> usize {
+        self.rows.iter().map(Vec::len).max().unwrap_or(0)
+    }
+
+    /// Walks back from `position` against `facing` to the last tile on the
+    /// other side of the board.
+    fn wrap_around(&self, mut position: Position, facing: Facing) -> Position {
+        let back = facing.turn(Turn::Left).turn(Turn::Left);
+
+        while self[position.step(back)] != Tile::Void {
+            position = position.step(back);
+        }
+
+        position
+    }
+}
+
+impl std::ops::Index<Position> for Board {
+    type Output = Tile;
+
+    /// Positions outside the rows, including ones that wrapped below zero,
+    /// are void.
+    fn index(&self, index: Position) -> &Self::Output {
+        self.rows
+            .get(index.y)
+            .and_then(|row| row.get(index.x))
+            .unwrap_or(&Tile::Void)
+    }
+}
+
+impl Facing {
+    const ALL: [Facing; 4] = [Facing::Right, Facing::Down, Facing::Left, Facing::Up];
+
+    fn turn(self, turn: Turn) -> Self {
+        let offset = match turn {
+            Turn::Left => 3,
+            Turn::Right => 1,
+        };
+
+        Self::ALL[(self as usize + offset) % 4]
+    }
+}
+
+impl Position {
+    fn step(self, facing: Facing) -> Self {
+        match facing {
+            Facing::Right => Self {
+                x: self.x + 1,
+                y: self.y,
+            },
+            Facing::Down => Self {
+                x: self.x,
+                y: self.y + 1,
+            },
+            Facing::Left => Self {
+                x: self.x.wrapping_sub(1),
+                y: self.y,
+            },
+            Facing::Up => Self {
+                x: self.x,
+                y: self.y.wrapping_sub(1),
+            },
+        }
+    }
+}
+
+impl Cube {
+    /// Folds the net on the board into a cube, whichever of the 11 nets it
+    /// is. The first face is laid flat, and every face reached from it across
+    /// an edge of the net is turned 90 degrees about that edge.
+    fn fold(board: &Board) -> Result<Self, SolutionError> {
+        let not_a_net = || SolutionError::invalid_input("the board is not the net of a cube");
+
+        let tiles = board
+            .rows
+            .iter()
+            .flatten()
+            .filter(|&&x| x != Tile::Void)
+            .count();
+        let size = (1..).find(|x| 6 * x * x >= tiles).unwrap();
+        if 6 * size * size != tiles {
+            return Err(not_a_net());
+        }
+
+        let squares: Vec<_> = (0..board.rows.len() / size)
+            .flat_map(|y| (0..board.width() / size).map(move |x| Position { x, y }))
+            .filter(|square| {
+                board[Position {
+                    x: square.x * size,
+                    y: square.y * size,
+                }] != Tile::Void
+            })
+            .collect();
+        if squares.len() != 6 {
+            return Err(not_a_net());
+        }
+
+        let mut placed = HashMap::new();
+        let mut queue = VecDeque::new();
+        let first = Face {
+            origin: squares[0],
+            right: Vector::new(1, 0, 0),
+            down: Vector::new(0, 1, 0),
+            normal: Vector::new(0, 0, 1),
+        };
+        placed.insert(squares[0], first);
+        queue.push_back(first);
+
+        while let Some(face) = queue.pop_front() {
+            for facing in Facing::ALL {
+                let square = face.origin.step(facing);
+
+                if squares.contains(&square) && !placed.contains_key(&square) {
+                    let neighbour = face.fold(facing, square);
+                    placed.insert(square, neighbour);
+                    queue.push_back(neighbour);
+                }
+            }
+        }
+
+        if placed.len() != squares.len() {
+            return Err(not_a_net());
+        }
+
+        let faces: Vec<_> = squares
+            .iter()
+            .map(|square| {
+                let face = placed[square];
+                Face {
+                    origin: Position {
+                        x: square.x * size,
+                        y: square.y * size,
+                    },
+                    ..face
+                }
+            })
+            .collect();
+
+        if faces
+            .iter()
+            .enumerate()
+            .any(|(i, a)| faces[..i].iter().any(|b| a.normal == b.normal))
+        {
+            return Err(not_a_net());
+        }
+
+        Ok(Self { size, faces })
+    }
+
+    /// Moves from the edge of a face onto the adjacent face, returning the
+    /// first position on it and the facing that continues in the same
+    /// direction around the cube.
+    ///
+    /// Works in coordinates where the cube spans from `-size` to `size` on
+    /// every axis, so that the center of every tile is a whole point.
+    fn cross_edge(&self, position: Position, facing: Facing) -> (Position, Facing) {
+        let size = self.size as i64;
+        let from = self
+            .faces
+            .iter()
+            .find(|x| x.contains(position, self.size))
+            .expect("the position is on a face");
+        let heading = from.direction(facing);
+        let to = self
+            .faces
+            .iter()
+            .find(|x| x.normal == heading)
+            .expect("every direction has a face");
+
+        let (column, row) = (
+            (position.x - from.origin.x) as i64,
+            (position.y - from.origin.y) as i64,
+        );
+        let point = from.normal * size
+            + from.right * (2 * column + 1 - size)
+            + from.down * (2 * row + 1 - size);
+        let next = point - from.normal + to.normal;
+
+        let new_facing = Facing::ALL
+            .into_iter()
+            .find(|&x| to.direction(x) == -from.normal)
+            .expect("the edge is shared with the face");
+        let column = ((next.dot(to.right) + size - 1) / 2) as usize;
+        let row = ((next.dot(to.down) + size - 1) / 2) as usize;
+
+        (
+            Position {
+                x: to.origin.x + column,
+                y: to.origin.y + row,
+            },
+            new_facing,
+        )
+    }
+}
+
+impl Face {
+    fn contains(&self, position: Position, size: usize) -> bool {
+        (self.origin.x..self.origin.x + size).contains(&position.x)
+            && (self.origin.y..self.origin.y + size).contains(&position.y)
+    }
+
+    fn direction(&self, facing: Facing) -> Vector {
+        match facing {
+            Facing::Right => self.right,
+            Facing::Down => self.down,
+            Facing::Left => -self.right,
+            Facing::Up => -self.down,
+        }
+    }
+
+    /// The face across the edge in the direction of `facing`, placed at
+    /// `origin`. Folding turns the direction of the edge into the normal of
+    /// the new face, and the old normal into the way back.
+    fn fold(&self, facing: Facing, origin: Position) -> Self {
+        let normal = self.direction(facing);
+        let (right, down) = match facing {
+            Facing::Right => (-self.normal, self.down),
+            Facing::Left => (self.normal, self.down),
+            Facing::Down => (self.right, -self.normal),
+            Facing::Up => (self.right, self.normal),
+        };
+
+        Self {
+            origin,
+            right,
+            down,
+            normal,
+        }
+    }
+}
+
+impl Vector {
+    const fn new(x: i64, y: i64, z: i64) -> Self {
+        Self { x, y, z }
+    }
+
+    fn dot(self, other: Self) -> i64 {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+}
+
+impl Add for Vector {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl Sub for Vector {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self + -other
+    }
+}
+
+impl Neg for Vector {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl Mul<i64> for Vector {
+    type Output = Self;
+
+    fn mul(self, factor: i64) -> Self {
+        Self::new(self.x * factor, self.y * factor, self.z * factor)
+    }
+}
+
+fn board(input: &str) -> IResult<&str, Board> {
+    map(line_separated(many1(tile)), |rows| Board { rows })(input)
+}
+
+fn tile(input: &str) -> IResult<&str, Tile> {
+    context(
+        "tile",
+        alt((
+            map(char(' '), |_| Tile::Void),
+            map(char('.'), |_| Tile::Open),
+            map(char('#'), |_| Tile::Wall),
+        )),
+    )(input)
+}
+
+fn path(input: &str) -> IResult<&str, Vec<Instruction>> {
+    many1(context(
+        "instruction",
+        alt((
+            map(integer, Instruction::Forward),
+            map(char('L'), |_| Instruction::Turn(Turn::Left)),
+            map(char('R'), |_| Instruction::Turn(Turn::Right)),
+        )),
+    ))(input)
+}
@@ -1,26 +1,286 @@
-use crate::Solution;
+use crate::{
+    parsing::{all_lines, ParseError, ParseFinish},
+    Solution, SolutionError,
+};
+use nom::{branch::alt, character::complete::char, combinator::map, error::context, multi::many1};
 
 #[derive(Default)]
 pub struct Day23 {}
 
+const PART_1_ROUNDS: usize = 10;
+
 impl Solution for Day23 {
-    type Part1Result = String;
+    type Part1Result = usize;
     type Part2Result = Self::Part1Result;
 
-    type Input = ();
+    type Input = Field;
+
+    fn parse(&mut self, input: &str) -> Result<Self::Input, ParseError> {
+        map(
+            all_lines(many1(context(
+                "tile",
+                alt((map(char('#'), |_| true), map(char('.'), |_| false))),
+            ))),
+            |rows| Field::new(&rows),
+        )(input)
+        .finish_parse(input)
+    }
+
+    fn run_part_1(&mut self, data: &Self::Input) -> Result<Self::Part1Result, SolutionError> {
+        let mut field = data.clone();
+
+        for round in 0..PART_1_ROUNDS {
+            field.round(round);
+        }
+
+        field.empty_tiles()
+    }
+
+    fn run_part_2(&mut self, data: &Self::Input) -> Result<Self::Part2Result, SolutionError> {
+        let mut field = data.clone();
+        let mut round = 0;
+
+        while field.round(round) {
+            round += 1;
+        }
+
+        Ok(round + 1)
+    }
+}
+
+/// Directions in the order the elves consider them in the first round.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    North,
+    South,
+    West,
+    East,
+}
+
+/// The elves as one bitset per row, where bit `x` of a row is set if an elf
+/// stands in column `x`. The field keeps an empty margin around the elves,
+/// and grows by a row or by a word of columns when they reach it.
+///
+/// The proposals of every row are kept between rounds and overwritten word by
+/// word, so that a round only allocates when the field grows.
+#[derive(Debug, Clone)]
+pub struct Field {
+    rows: Vec<Row>,
+    /// The elves of each row that propose to move in each direction, in the
+    /// order of [`Direction::ALL`].
+    proposals: Vec<[Row; 4]>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Row {
+    words: Vec<u64>,
+}
+
+impl Direction {
+    const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::South,
+        Direction::West,
+        Direction::East,
+    ];
+}
+
+impl Field {
+    fn new(tiles: &[Vec<bool>]) -> Self {
+        let width = tiles.iter().map(Vec::len).max().unwrap_or(0);
+        let mut rows: Vec<_> = tiles
+            .iter()
+            .map(|tiles| {
+                let mut row = Row::empty(width / 64 + 1);
+                for (x, _) in tiles.iter().enumerate().filter(|(_, &elf)| elf) {
+                    row.words[x / 64] |= 1 << (x % 64);
+                }
+                row
+            })
+            .collect();
 
-    fn parse<'a>(
-        &mut self,
-        _input: &'a str,
-    ) -> Result<Self::Input, nom::Err<nom::error::Error<&'a str>>> {
-        todo!()
+        if rows.is_empty() {
+            rows.push(Row::empty(1));
+        }
+
+        Self {
+            rows,
+            proposals: vec![],
+        }
+    }
+
+    fn width_in_words(&self) -> usize {
+        self.rows[0].words.len()
     }
 
-    fn run_part_1(&mut self, _data: &Self::Input) -> Self::Part1Result {
-        todo!()
+    /// Makes sure that the first and last rows and columns are empty, so that
+    /// no elf can move off the field.
+    fn ensure_margin(&mut self) {
+        if !self.rows[0].is_empty() {
+            self.rows.insert(0, Row::empty(self.width_in_words()));
+        }
+        if !self.rows.last().unwrap().is_empty() {
+            self.rows.push(Row::empty(self.width_in_words()));
+        }
+        if self.rows.iter().any(|x| x.words[0] & 1 != 0) {
+            self.rows.iter_mut().for_each(|x| x.words.insert(0, 0));
+        }
+        if self.rows.iter().any(|x| x.words.last().unwrap() >> 63 != 0) {
+            self.rows.iter_mut().for_each(|x| x.words.push(0));
+        }
     }
 
-    fn run_part_2(&mut self, _data: &Self::Input) -> Self::Part2Result {
-        todo!()
+    /// Runs a round in which the elves consider the directions starting with
+    /// the `round`-th one, and returns whether any elf moved.
+    fn round(&mut self, round: usize) -> bool {
+        self.ensure_margin();
+
+        let width = self.width_in_words();
+        self.proposals
+            .resize_with(self.rows.len(), || [(); 4].map(|_| Row::empty(width)));
+        for row in self.proposals.iter_mut().flatten() {
+            row.words.resize(width, 0);
+        }
+
+        for y in 0..self.rows.len() {
+            self.propose(y, round);
+        }
+
+        let Self { rows, proposals } = self;
+        // Rows out of range, including ones that wrapped below zero, have no
+        // proposals.
+        let proposal = |y: usize, direction: Direction, i: usize| {
+            proposals
+                .get(y)
+                .map_or(0, |x| x[direction as usize].words[i])
+        };
+        let mut moved = false;
+
+        for (y, row) in rows.iter_mut().enumerate() {
+            let [north, south, west, east] = &proposals[y];
+
+            for (i, word) in row.words.iter_mut().enumerate() {
+                // Two elves can only propose the same tile when they come
+                // from opposite sides, as any other pair would see each
+                // other.
+                let blocked = (north.words[i] & proposal(y.wrapping_sub(2), Direction::South, i))
+                    | (south.words[i] & proposal(y + 2, Direction::North, i))
+                    | (west.words[i] & east.shifted_east(i, 2))
+                    | (east.words[i] & west.shifted_west(i, 2));
+                let arrivals = (proposal(y + 1, Direction::North, i)
+                    ^ proposal(y.wrapping_sub(1), Direction::South, i))
+                    | (west.shifted_west(i, 1) ^ east.shifted_east(i, 1));
+                let proposing = north.words[i] | south.words[i] | west.words[i] | east.words[i];
+
+                moved |= arrivals != 0;
+                *word = (*word & !proposing) | blocked | arrivals;
+            }
+        }
+
+        moved
+    }
+
+    /// Fills in the proposals of the elves in row `y`.
+    fn propose(&mut self, y: usize, round: usize) {
+        let row = |y: usize| self.rows.get(y);
+        let (north, current, south) = (row(y.wrapping_sub(1)), &self.rows[y], row(y + 1));
+        let column = |i| {
+            north.map_or(0, |x| x.words[i]) | current.words[i] | south.map_or(0, |x| x.words[i])
+        };
+        let around = |row: Option<&Row>, i| {
+            row.map_or(0, |x| {
+                x.words[i] | x.shifted_east(i, 1) | x.shifted_west(i, 1)
+            })
+        };
+        let last = self.width_in_words() - 1;
+
+        for i in 0..=last {
+            let occupied = [
+                around(north, i),
+                around(south, i),
+                column(i) << 1 | if i > 0 { column(i - 1) >> 63 } else { 0 },
+                column(i) >> 1 | if i < last { column(i + 1) << 63 } else { 0 },
+            ];
+            let crowded = occupied.iter().fold(0, |acc, x| acc | x);
+            let mut undecided = current.words[i] & crowded;
+
+            for j in 0..Direction::ALL.len() {
+                let direction = Direction::ALL[(round + j) % Direction::ALL.len()] as usize;
+                let proposal = undecided & !occupied[direction];
+                self.proposals[y][direction].words[i] = proposal;
+                undecided &= !proposal;
+            }
+        }
+    }
+
+    /// Counts the empty tiles within the smallest rectangle around the elves.
+    fn empty_tiles(&self) -> Result<usize, SolutionError> {
+        let occupied: Vec<_> = self
+            .rows
+            .iter()
+            .enumerate()
+            .filter(|(_, x)| !x.is_empty())
+            .collect();
+        let (Some(&(top, _)), Some(&(bottom, _))) = (occupied.first(), occupied.last()) else {
+            return Err(SolutionError::invalid_input("there are no elves"));
+        };
+        let left = occupied
+            .iter()
+            .filter_map(|(_, x)| x.first())
+            .min()
+            .unwrap();
+        let right = occupied.iter().filter_map(|(_, x)| x.last()).max().unwrap();
+        let elves: usize = occupied.iter().map(|(_, x)| x.count()).sum();
+
+        Ok((bottom - top + 1) * (right - left + 1) - elves)
+    }
+}
+
+impl Row {
+    fn empty(words: usize) -> Self {
+        Self {
+            words: vec![0; words],
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.words.iter().all(|&x| x == 0)
+    }
+
+    fn count(&self) -> usize {
+        self.words.iter().map(|x| x.count_ones() as usize).sum()
+    }
+
+    /// Column of the westernmost elf.
+    fn first(&self) -> Option<usize> {
+        self.words
+            .iter()
+            .position(|&x| x != 0)
+            .map(|i| i * 64 + self.words[i].trailing_zeros() as usize)
+    }
+
+    /// Column of the easternmost elf.
+    fn last(&self) -> Option<usize> {
+        self.words
+            .iter()
+            .rposition(|&x| x != 0)
+            .map(|i| i * 64 + 63 - self.words[i].leading_zeros() as usize)
+    }
+
+    /// Word `i` of the row with every elf moved `by` columns east.
+    fn shifted_east(&self, i: usize, by: u32) -> u64 {
+        let carry = match i {
+            0 => 0,
+            _ => self.words[i - 1] >> (64 - by),
+        };
+
+        self.words[i] << by | carry
+    }
+
+    /// Word `i` of the row with every elf moved `by` columns west.
+    fn shifted_west(&self, i: usize, by: u32) -> u64 {
+        let carry = self.words.get(i + 1).map_or(0, |x| x << (64 - by));
+
+        self.words[i] >> by | carry
     }
 }
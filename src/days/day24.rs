@@ -1,26 +1,370 @@
-use crate::Solution;
+use crate::{
+    parsing::{all_lines, IResult, ParseError, ParseFinish},
+    Solution, SolutionError,
+};
+use itertools::Itertools;
+use nom::{
+    branch::alt,
+    character::complete::char,
+    combinator::{map, map_opt},
+    error::context,
+    multi::many1,
+};
+use std::{
+    collections::HashSet,
+    fmt::{self, Display},
+};
 
 #[derive(Default)]
 pub struct Day24 {}
 
 impl Solution for Day24 {
-    type Part1Result = String;
+    type Part1Result = usize;
     type Part2Result = Self::Part1Result;
 
-    type Input = ();
+    type Input = Valley;
 
-    fn parse<'a>(
-        &mut self,
-        _input: &'a str,
-    ) -> Result<Self::Input, nom::Err<nom::error::Error<&'a str>>> {
-        todo!()
+    fn parse(&mut self, input: &str) -> Result<Self::Input, ParseError> {
+        context(
+            "valley surrounded by walls with an entrance and an exit",
+            map_opt(all_lines(many1(tile)), |x| Valley::new(&x)),
+        )(input)
+        .finish_parse(input)
     }
 
-    fn run_part_1(&mut self, _data: &Self::Input) -> Self::Part1Result {
-        todo!()
+    fn run_part_1(&mut self, data: &Self::Input) -> Result<Self::Part1Result, SolutionError> {
+        let occupancies = data.occupancies();
+        trip(&occupancies, data.entrance(), data.exit(), 0)
     }
 
-    fn run_part_2(&mut self, _data: &Self::Input) -> Self::Part2Result {
-        todo!()
+    fn run_part_2(&mut self, data: &Self::Input) -> Result<Self::Part2Result, SolutionError> {
+        let occupancies = data.occupancies();
+        let there = trip(&occupancies, data.entrance(), data.exit(), 0)?;
+        let back = trip(&occupancies, data.exit(), data.entrance(), there)?;
+        trip(&occupancies, data.entrance(), data.exit(), back)
     }
 }
+
+/// Finds the earliest minute at which `to` can be reached from `from` when
+/// leaving at `start`, by expanding every position reachable in one minute
+/// into those reachable in the next.
+///
+/// Blizzards repeat every [`Valley::period`] minutes, so standing on a
+/// position at two minutes a period apart leads to the same futures, and
+/// there are only finitely many states to visit before giving up.
+fn trip(
+    occupancies: &[Occupancy],
+    from: Position,
+    to: Position,
+    start: usize,
+) -> Result<usize, SolutionError> {
+    let period = occupancies.len();
+    let mut reachable = vec![from];
+    let mut visited = HashSet::from([(from, start % period)]);
+
+    for minute in start.. {
+        if reachable.contains(&to) {
+            return Ok(minute);
+        }
+        if reachable.is_empty() {
+            break;
+        }
+
+        let occupancy = &occupancies[(minute + 1) % period];
+        let mut next = vec![];
+
+        for position in reachable {
+            for candidate in position.moves() {
+                if occupancy.is_clear(candidate.x, candidate.y)
+                    && visited.insert((candidate, (minute + 1) % period))
+                {
+                    next.push(candidate);
+                }
+            }
+        }
+
+        reachable = next;
+    }
+
+    Err(SolutionError::no_solution(
+        "the blizzards block every way to the destination",
+    ))
+}
+
+/// The valley between its walls, with the blizzards where they start.
+///
+/// Positions count from the top left wall, so the entrance is in row 0 and
+/// the exit in row `height + 1`.
+#[derive(Debug, Clone)]
+pub struct Valley {
+    width: usize,
+    height: usize,
+    entrance_column: usize,
+    exit_column: usize,
+    blizzards: Vec<Blizzard>,
+}
+
+/// The blizzards in the valley at one minute, which displays the way the
+/// puzzle draws the valley.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Occupancy {
+    width: usize,
+    height: usize,
+    entrance_column: usize,
+    exit_column: usize,
+    /// The directions of the blizzards on every tile inside the walls, as
+    /// flags of [`Direction::flag`], row by row.
+    tiles: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Blizzard {
+    position: Position,
+    direction: Direction,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Position {
+    x: usize,
+    y: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tile {
+    Wall,
+    Ground,
+    Blizzard(Direction),
+}
+
+impl Valley {
+    fn new(rows: &[Vec<Tile>]) -> Option<Self> {
+        let (top, bottom) = (rows.first()?, rows.last()?);
+        // Without a tile inside the walls, the blizzards have no period.
+        let width = top.len().checked_sub(2).filter(|&x| x > 0)?;
+        let height = rows.len().checked_sub(2).filter(|&x| x > 0)?;
+
+        let opening = |row: &[Tile]| {
+            let mut openings = row.iter().positions(|&x| x == Tile::Ground);
+            openings.next().filter(|_| openings.next().is_none())
+        };
+        if bottom.len() != width + 2 {
+            return None;
+        }
+
+        let entrance_column = opening(top)?;
+        let exit_column = opening(bottom)?;
+
+        let mut blizzards = vec![];
+
+        for (y, row) in rows.iter().enumerate().skip(1).take(height) {
+            let is_walled = row.len() == width + 2
+                && row.first() == Some(&Tile::Wall)
+                && row.last() == Some(&Tile::Wall);
+            if !is_walled {
+                return None;
+            }
+
+            for (x, &tile) in row.iter().enumerate().skip(1).take(width) {
+                match tile {
+                    Tile::Wall => return None,
+                    Tile::Ground => (),
+                    Tile::Blizzard(direction) => blizzards.push(Blizzard {
+                        position: Position { x, y },
+                        direction,
+                    }),
+                }
+            }
+        }
+
+        Some(Self {
+            width,
+            height,
+            entrance_column,
+            exit_column,
+            blizzards,
+        })
+    }
+
+    /// Number of minutes after which every blizzard is back where it
+    /// started.
+    pub fn period(&self) -> usize {
+        lcm(self.width, self.height)
+    }
+
+    /// The blizzards after `minute` minutes.
+    pub fn occupancy(&self, minute: usize) -> Occupancy {
+        let mut tiles = vec![0; self.width * self.height];
+
+        for blizzard in self.blizzards.iter() {
+            // Inside the walls, counting from 0.
+            let (x, y) = (blizzard.position.x - 1, blizzard.position.y - 1);
+            let (x, y) = match blizzard.direction {
+                Direction::Up => (x, (y + self.height - minute % self.height) % self.height),
+                Direction::Down => (x, (y + minute) % self.height),
+                Direction::Left => ((x + self.width - minute % self.width) % self.width, y),
+                Direction::Right => ((x + minute) % self.width, y),
+            };
+
+            tiles[y * self.width + x] |= blizzard.direction.flag();
+        }
+
+        Occupancy {
+            width: self.width,
+            height: self.height,
+            entrance_column: self.entrance_column,
+            exit_column: self.exit_column,
+            tiles,
+        }
+    }
+
+    /// The blizzards at every minute of one period.
+    fn occupancies(&self) -> Vec<Occupancy> {
+        (0..self.period()).map(|x| self.occupancy(x)).collect()
+    }
+
+    fn entrance(&self) -> Position {
+        Position {
+            x: self.entrance_column,
+            y: 0,
+        }
+    }
+
+    fn exit(&self) -> Position {
+        Position {
+            x: self.exit_column,
+            y: self.height + 1,
+        }
+    }
+}
+
+impl Occupancy {
+    /// Whether the tile in column `x` and row `y`, counting from the top left
+    /// wall, can be stood on: the entrance, the exit, or a tile inside the
+    /// walls without a blizzard. Walls and positions outside the valley are
+    /// never clear.
+    pub fn is_clear(&self, x: usize, y: usize) -> bool {
+        match self.tile(x, y) {
+            Some(tile) => tile == 0,
+            None => {
+                (x, y) == (self.entrance_column, 0) || (x, y) == (self.exit_column, self.height + 1)
+            }
+        }
+    }
+
+    /// The blizzards on the tile in column `x` and row `y`, if it is inside the
+    /// walls.
+    fn tile(&self, x: usize, y: usize) -> Option<u8> {
+        let is_inside = (1..=self.width).contains(&x) && (1..=self.height).contains(&y);
+        is_inside.then(|| self.tiles[(y - 1) * self.width + x - 1])
+    }
+}
+
+impl Display for Occupancy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let wall_with_opening = |f: &mut fmt::Formatter<'_>, column| {
+            for x in 0..self.width + 2 {
+                write!(f, "{}", if x == column { '.' } else { '#' })?;
+            }
+            writeln!(f)
+        };
+
+        wall_with_opening(f, self.entrance_column)?;
+
+        for row in self.tiles.chunks(self.width) {
+            write!(f, "#")?;
+
+            for &tile in row {
+                let direction = Direction::ALL.into_iter().find(|x| x.flag() == tile);
+
+                match (tile.count_ones(), direction) {
+                    (0, _) => write!(f, ".")?,
+                    (1, Some(direction)) => write!(f, "{}", direction.symbol())?,
+                    (count, _) => write!(f, "{count}")?,
+                }
+            }
+
+            writeln!(f, "#")?;
+        }
+
+        wall_with_opening(f, self.exit_column)
+    }
+}
+
+impl Position {
+    /// The positions after waiting or moving one step in any direction.
+    fn moves(self) -> [Position; 5] {
+        [
+            self,
+            Position {
+                x: self.x,
+                y: self.y.wrapping_sub(1),
+            },
+            Position {
+                x: self.x,
+                y: self.y + 1,
+            },
+            Position {
+                x: self.x.wrapping_sub(1),
+                y: self.y,
+            },
+            Position {
+                x: self.x + 1,
+                y: self.y,
+            },
+        ]
+    }
+}
+
+impl Direction {
+    const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+    ];
+
+    fn flag(self) -> u8 {
+        1 << self as u8
+    }
+
+    fn symbol(self) -> char {
+        match self {
+            Direction::Up => '^',
+            Direction::Down => 'v',
+            Direction::Left => '<',
+            Direction::Right => '>',
+        }
+    }
+}
+
+fn lcm(a: usize, b: usize) -> usize {
+    let (mut x, mut y) = (a, b);
+
+    while y != 0 {
+        (x, y) = (y, x % y);
+    }
+
+    a / x * b
+}
+
+fn tile(input: &str) -> IResult<&str, Tile> {
+    context(
+        "tile",
+        alt((
+            map(char('#'), |_| Tile::Wall),
+            map(char('.'), |_| Tile::Ground),
+            map(char('^'), |_| Tile::Blizzard(Direction::Up)),
+            map(char('v'), |_| Tile::Blizzard(Direction::Down)),
+            map(char('<'), |_| Tile::Blizzard(Direction::Left)),
+            map(char('>'), |_| Tile::Blizzard(Direction::Right)),
+        )),
+    )(input)
+}
@@ -1,26 +1,33 @@
-use crate::Solution;
+use crate::{
+    parsing::{all_lines, IResult, ParseError, ParseFinish},
+    snafu::Snafu,
+    Solution, SolutionError,
+};
+use nom::{bytes::complete::is_a, combinator::map_res, error::context};
 
 #[derive(Default)]
 pub struct Day25 {}
 
 impl Solution for Day25 {
-    type Part1Result = String;
+    type Part1Result = Snafu;
     type Part2Result = Self::Part1Result;
 
-    type Input = ();
+    type Input = Vec<Snafu>;
 
-    fn parse<'a>(
-        &mut self,
-        _input: &'a str,
-    ) -> Result<Self::Input, nom::Err<nom::error::Error<&'a str>>> {
-        todo!()
+    fn parse(&mut self, input: &str) -> Result<Self::Input, ParseError> {
+        all_lines(snafu)(input).finish_parse(input)
     }
 
-    fn run_part_1(&mut self, _data: &Self::Input) -> Self::Part1Result {
-        todo!()
+    fn run_part_1(&mut self, data: &Self::Input) -> Result<Self::Part1Result, SolutionError> {
+        Ok(data.iter().sum())
     }
 
-    fn run_part_2(&mut self, _data: &Self::Input) -> Self::Part2Result {
-        todo!()
+    /// The last day only has one puzzle.
+    fn run_part_2(&mut self, _data: &Self::Input) -> Result<Self::Part2Result, SolutionError> {
+        Err(SolutionError::NoSuchPart)
     }
 }
+
+fn snafu(input: &str) -> IResult<&str, Snafu> {
+    context("SNAFU number", map_res(is_a("=-012"), str::parse))(input)
+}
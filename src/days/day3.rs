@@ -1,11 +1,7 @@
-use crate::parsing::line_separated;
-use crate::Solution;
-use nom::{
-    character::complete::satisfy,
-    combinator::{all_consuming, map},
-    multi::many1,
-    IResult,
-};
+use crate::parsing::{all_lines, IResult, ParseError, ParseFinish};
+use crate::{Solution, SolutionError};
+use itertools::Itertools;
+use nom::{character::complete::satisfy, combinator::map, error::context, multi::many1};
 use std::collections::HashSet;
 
 #[derive(Default)]
@@ -17,20 +13,39 @@ impl Solution for Day3 {
 
     type Input = Vec<Rucksack>;
 
-    fn parse<'a>(
-        &mut self,
-        input: &'a str,
-    ) -> Result<Self::Input, nom::Err<nom::error::Error<&'a str>>> {
-        all_consuming(line_separated(rucksack))(input).map(|x| x.1)
+    fn parse(&mut self, input: &str) -> Result<Self::Input, ParseError> {
+        all_lines(rucksack)(input).finish_parse(input)
     }
 
-    fn run_part_1(&mut self, data: &Self::Input) -> Self::Part1Result {
-        data.iter().map(|r| r.common_item().priority()).sum()
+    fn run_part_1(&mut self, data: &Self::Input) -> Result<Self::Part1Result, SolutionError> {
+        data.iter()
+            .enumerate()
+            .map(|(i, r)| {
+                let item = r.common_item().ok_or_else(|| {
+                    SolutionError::invalid_input(format!(
+                        "the compartments of rucksack {} do not share exactly one item",
+                        i + 1
+                    ))
+                })?;
+
+                Ok(item.priority())
+            })
+            .sum()
     }
 
-    fn run_part_2(&mut self, data: &Self::Input) -> Self::Part2Result {
+    fn run_part_2(&mut self, data: &Self::Input) -> Result<Self::Part2Result, SolutionError> {
         data.chunks(3)
-            .map(|xs| common_item_in_rucksacks(xs).priority())
+            .enumerate()
+            .map(|(i, xs)| {
+                let item = common_item_in_rucksacks(xs).ok_or_else(|| {
+                    SolutionError::invalid_input(format!(
+                        "the rucksacks of group {} do not share exactly one item",
+                        i + 1
+                    ))
+                })?;
+
+                Ok(item.priority())
+            })
             .sum()
     }
 }
@@ -62,14 +77,9 @@ impl Rucksack {
         }
     }
 
-    fn common_item(&self) -> Item {
+    fn common_item(&self) -> Option<Item> {
         let intersection = &self.compartment_1.items & &self.compartment_2.items;
-
-        if intersection.len() != 1 {
-            panic!("intersection of compartments is not of size 1: {self:?}");
-        }
-
-        intersection.into_iter().next().unwrap()
+        intersection.into_iter().exactly_one().ok()
     }
 
     fn items(&self) -> HashSet<Item> {
@@ -100,24 +110,13 @@ fn rucksack(input: &str) -> IResult<&str, Rucksack> {
 }
 
 fn item(input: &str) -> IResult<&str, Item> {
-    map(satisfy(|x| x.is_ascii_alphabetic()), |x| Item { char: x })(input)
+    context(
+        "item",
+        map(satisfy(|x| x.is_ascii_alphabetic()), |x| Item { char: x }),
+    )(input)
 }
 
-fn common_item_in_rucksacks(sacks: &[Rucksack]) -> Item {
-    assert!(!sacks.is_empty());
-
-    let intersection = sacks
-        .iter()
-        .map(|r| r.items())
-        .reduce(|a, x| &a & &x)
-        .unwrap();
-
-    if intersection.len() != 1 {
-        panic!(
-            "intersection of sacks is not of size 1 but {}: {sacks:?}",
-            intersection.len()
-        );
-    }
-
-    intersection.into_iter().next().unwrap()
+fn common_item_in_rucksacks(sacks: &[Rucksack]) -> Option<Item> {
+    let intersection = sacks.iter().map(|r| r.items()).reduce(|a, x| &a & &x)?;
+    intersection.into_iter().exactly_one().ok()
 }
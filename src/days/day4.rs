@@ -1,13 +1,8 @@
 use crate::{
-    parsing::{integer, line_separated},
-    Solution,
-};
-use nom::{
-    character::complete::char as nom_char,
-    combinator::{all_consuming, map},
-    sequence::separated_pair,
-    IResult,
+    parsing::{all_lines, integer, IResult, ParseError, ParseFinish},
+    Solution, SolutionError,
 };
+use nom::{character::complete::char as nom_char, combinator::map, sequence::separated_pair};
 
 #[derive(Default)]
 pub struct Day4 {}
@@ -18,19 +13,16 @@ impl Solution for Day4 {
 
     type Input = Vec<ElfPair>;
 
-    fn parse<'a>(
-        &mut self,
-        input: &'a str,
-    ) -> Result<Self::Input, nom::Err<nom::error::Error<&'a str>>> {
-        all_consuming(line_separated(elf_pair))(input).map(|x| x.1)
+    fn parse(&mut self, input: &str) -> Result<Self::Input, ParseError> {
+        all_lines(elf_pair)(input).finish_parse(input)
     }
 
-    fn run_part_1(&mut self, data: &Self::Input) -> Self::Part1Result {
-        data.iter().filter(|x| x.has_containing_ranges()).count()
+    fn run_part_1(&mut self, data: &Self::Input) -> Result<Self::Part1Result, SolutionError> {
+        Ok(data.iter().filter(|x| x.has_containing_ranges()).count())
     }
 
-    fn run_part_2(&mut self, data: &Self::Input) -> Self::Part2Result {
-        data.iter().filter(|x| x.has_overlapping_ranges()).count()
+    fn run_part_2(&mut self, data: &Self::Input) -> Result<Self::Part2Result, SolutionError> {
+        Ok(data.iter().filter(|x| x.has_overlapping_ranges()).count())
     }
 }
 
@@ -1,15 +1,15 @@
 use crate::{
-    parsing::{integer, line_separated},
-    Solution,
+    parsing::{all_lines, integer, line_separated, IResult, ParseError, ParseFinish},
+    Solution, SolutionError,
 };
 use nom::{
     branch::alt,
     bytes::complete::tag,
-    character::complete::{anychar, char as nom_char, multispace0, space0},
-    combinator::{all_consuming, map},
+    character::complete::{anychar, char as nom_char, multispace0, space1},
+    combinator::{consumed, fail, map},
+    error::context,
     multi::separated_list1,
     sequence::{delimited, preceded, tuple},
-    IResult,
 };
 
 #[derive(Default)]
@@ -21,44 +21,31 @@ impl Solution for Day5 {
 
     type Input = (Storage, Vec<Command>);
 
-    fn parse<'a>(
-        &mut self,
-        input: &'a str,
-    ) -> Result<Self::Input, nom::Err<nom::error::Error<&'a str>>> {
-        let (rem, mut rows) =
-            line_separated(separated_list1(nom_char(' '), parse_empty_or_crate))(input)?;
-        let (rem, mut stacks) =
-            separated_list1(space0, map(integer::<usize>, |_| Stack::new()))(rem)?;
-        let commands = all_consuming(preceded(multispace0, line_separated(parse_command)))(rem)
-            .map(|x| x.1)?;
-
-        rows.reverse();
-        for row in rows {
-            for (i, maybe_crate) in row.into_iter().enumerate() {
-                if let Some(cr) = maybe_crate {
-                    stacks[i].push(cr);
-                }
-            }
-        }
+    fn parse(&mut self, input: &str) -> Result<Self::Input, ParseError> {
+        let (stacks, commands) = tuple((
+            parse_stacks,
+            preceded(multispace0, all_lines(parse_command)),
+        ))(input)
+        .finish_parse(input)?;
 
         Ok((Storage { stacks }, commands))
     }
 
-    fn run_part_1(&mut self, data: &Self::Input) -> Self::Part1Result {
+    fn run_part_1(&mut self, data: &Self::Input) -> Result<Self::Part1Result, SolutionError> {
         let mut storage = data.0.clone();
 
         for command in data.1.iter() {
-            storage.execute(command, Order::Reverse);
+            storage.execute(command, Order::Reverse)?;
         }
 
         storage.signature()
     }
 
-    fn run_part_2(&mut self, data: &Self::Input) -> Self::Part2Result {
+    fn run_part_2(&mut self, data: &Self::Input) -> Result<Self::Part2Result, SolutionError> {
         let mut storage = data.0.clone();
 
         for command in data.1.iter() {
-            storage.execute(command, Order::Retain);
+            storage.execute(command, Order::Retain)?;
         }
 
         storage.signature()
@@ -94,11 +81,19 @@ enum Order {
 }
 
 impl Storage {
-    fn execute(&mut self, cmd: &Command, order: Order) {
+    fn execute(&mut self, cmd: &Command, order: Order) -> Result<(), SolutionError> {
         let mut buffer = Vec::with_capacity(cmd.count);
+        let from = self.stack_mut(cmd.from)?;
 
         for _ in 0..cmd.count {
-            let cr = self.stacks[cmd.from - 1].pop();
+            let cr = from.pop().ok_or_else(|| {
+                SolutionError::invalid_input(format!(
+                    "cannot move {} crates from stack {}, it only holds {}",
+                    cmd.count,
+                    cmd.from,
+                    buffer.len()
+                ))
+            })?;
             buffer.push(cr);
         }
 
@@ -106,19 +101,43 @@ impl Storage {
             buffer.reverse();
         }
 
+        let to = self.stack_mut(cmd.to)?;
+
         for cr in buffer {
-            self.stacks[cmd.to - 1].push(cr);
+            to.push(cr);
         }
-    }
 
-    fn signature(&self) -> String {
-        let mut sig = String::with_capacity(self.stacks.len());
+        Ok(())
+    }
 
-        for stack in self.stacks.iter() {
-            sig.push(stack.top().id);
-        }
+    fn stack_mut(&mut self, number: usize) -> Result<&mut Stack, SolutionError> {
+        let count = self.stacks.len();
+
+        number
+            .checked_sub(1)
+            .and_then(|i| self.stacks.get_mut(i))
+            .ok_or_else(|| {
+                SolutionError::invalid_input(format!(
+                    "there is no stack {number}, only stacks 1 to {count}"
+                ))
+            })
+    }
 
-        sig
+    /// The crates on top of the stacks, which only spell the message when no
+    /// stack is empty.
+    fn signature(&self) -> Result<String, SolutionError> {
+        self.stacks
+            .iter()
+            .enumerate()
+            .map(|(i, stack)| {
+                stack.top().map(|cr| cr.id).ok_or_else(|| {
+                    SolutionError::invalid_input(format!(
+                        "stack {} ends up empty, so it has no crate in the message",
+                        i + 1
+                    ))
+                })
+            })
+            .collect()
     }
 }
 
@@ -131,28 +150,64 @@ impl Stack {
         self.crates.push(cr);
     }
 
-    fn pop(&mut self) -> Crate {
-        self.crates.pop().unwrap()
+    fn pop(&mut self) -> Option<Crate> {
+        self.crates.pop()
     }
 
-    fn top(&self) -> Crate {
-        *self.crates.last().unwrap()
+    fn top(&self) -> Option<Crate> {
+        self.crates.last().copied()
     }
 }
 
+/// Parses the drawing of the stacks, whose rows of crates may not be wider
+/// than the labels of the stacks below them.
+fn parse_stacks(input: &str) -> IResult<&str, Vec<Stack>> {
+    let (rest, (rows, labels)) = tuple((
+        line_separated(consumed(separated_list1(
+            nom_char(' '),
+            parse_empty_or_crate,
+        ))),
+        separated_list1(space1, integer::<usize>),
+    ))(input)?;
+
+    if let Some((text, _)) = rows.iter().find(|(_, row)| row.len() > labels.len()) {
+        // Every crate or gap takes four characters with its separator.
+        let extra = &text[4 * labels.len()..];
+        return context("end of the row after the last stack", fail)(extra);
+    }
+
+    let mut stacks: Vec<_> = labels.iter().map(|_| Stack::new()).collect();
+
+    for (_, row) in rows.into_iter().rev() {
+        for (stack, maybe_crate) in stacks.iter_mut().zip(row) {
+            if let Some(cr) = maybe_crate {
+                stack.push(cr);
+            }
+        }
+    }
+
+    Ok((rest, stacks))
+}
+
 fn parse_command(input: &str) -> IResult<&str, Command> {
-    map(
-        tuple((
-            preceded(tag("move "), integer),
-            preceded(tag(" from "), integer),
-            preceded(tag(" to "), integer),
-        )),
-        |(count, from, to)| Command { count, from, to },
+    context(
+        "move command",
+        map(
+            tuple((
+                preceded(tag("move "), integer),
+                preceded(tag(" from "), integer),
+                preceded(tag(" to "), integer),
+            )),
+            |(count, from, to)| Command { count, from, to },
+        ),
     )(input)
 }
 
 fn parse_empty_or_crate(input: &str) -> IResult<&str, Option<Crate>> {
-    alt((map(parse_crate, Some), map(tag("   "), |_| None)))(input)
+    context(
+        "crate or gap",
+        alt((map(parse_crate, Some), map(tag("   "), |_| None))),
+    )(input)
 }
 
 fn parse_crate(input: &str) -> IResult<&str, Crate> {
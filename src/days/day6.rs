@@ -1,4 +1,4 @@
-use crate::Solution;
+use crate::{ParseError, Solution, SolutionError};
 use std::collections::HashMap;
 
 #[derive(Default)]
@@ -10,19 +10,20 @@ impl Solution for Day6 {
 
     type Input = Vec<char>;
 
-    fn parse<'a>(
-        &mut self,
-        input: &'a str,
-    ) -> Result<Self::Input, nom::Err<nom::error::Error<&'a str>>> {
-        Ok(input.chars().into_iter().collect())
+    fn parse(&mut self, input: &str) -> Result<Self::Input, ParseError> {
+        Ok(input.chars().collect())
     }
 
-    fn run_part_1(&mut self, data: &Self::Input) -> Self::Part1Result {
-        find_marker(data, 4).unwrap() + 1
+    fn run_part_1(&mut self, data: &Self::Input) -> Result<Self::Part1Result, SolutionError> {
+        find_marker(data, 4)
+            .map(|x| x + 1)
+            .ok_or_else(|| SolutionError::no_solution("there is no start-of-packet marker"))
     }
 
-    fn run_part_2(&mut self, data: &Self::Input) -> Self::Part2Result {
-        find_marker(data, 14).unwrap() + 1
+    fn run_part_2(&mut self, data: &Self::Input) -> Result<Self::Part2Result, SolutionError> {
+        find_marker(data, 14)
+            .map(|x| x + 1)
+            .ok_or_else(|| SolutionError::no_solution("there is no start-of-message marker"))
     }
 }
 
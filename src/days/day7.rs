@@ -1,20 +1,33 @@
 use crate::{
-    parsing::{integer, line_separated},
-    Solution,
+    parsing::{all_lines, integer, IResult, ParseError, ParseFinish},
+    ParamError, Params, Solution, SolutionError,
 };
 use nom::{
     branch::alt,
     bytes::complete::tag,
     character::complete::{char, line_ending, multispace1, not_line_ending},
-    combinator::{all_consuming, map, not, peek},
+    combinator::{map, not, peek},
+    error::context,
     multi::separated_list0,
     sequence::{preceded, separated_pair},
-    IResult,
 };
 use std::{cell::RefCell, collections::HashMap, rc::Rc};
 
-#[derive(Default)]
-pub struct Day7 {}
+pub struct Day7 {
+    size_limit: usize,
+    total_size: usize,
+    size_requirement: usize,
+}
+
+impl Default for Day7 {
+    fn default() -> Self {
+        Self {
+            size_limit: 100_000,
+            total_size: 70_000_000,
+            size_requirement: 30_000_000,
+        }
+    }
+}
 
 impl Solution for Day7 {
     type Part1Result = usize;
@@ -22,40 +35,55 @@ impl Solution for Day7 {
 
     type Input = Vec<Command>;
 
-    fn parse<'a>(
-        &mut self,
-        input: &'a str,
-    ) -> Result<Self::Input, nom::Err<nom::error::Error<&'a str>>> {
-        all_consuming(line_separated(command))(input).map(|x| x.1)
+    fn configure(&mut self, params: &mut Params) -> Result<(), ParamError> {
+        params.apply("size_limit", &mut self.size_limit)?;
+        params.apply("total_size", &mut self.total_size)?;
+        params.apply("size_requirement", &mut self.size_requirement)
     }
 
-    fn run_part_1(&mut self, data: &Self::Input) -> Self::Part1Result {
-        const SIZE_LIMIT: usize = 100_000;
+    fn parse(&mut self, input: &str) -> Result<Self::Input, ParseError> {
+        all_lines(command)(input).finish_parse(input)
+    }
+
+    fn run_part_1(&mut self, data: &Self::Input) -> Result<Self::Part1Result, SolutionError> {
         let tree = Tree::build_from_commands(data);
         let result = walk_dir_sizes(&tree);
 
-        result
+        Ok(result
             .dir_sizes
             .into_iter()
-            .filter(|&x| x <= SIZE_LIMIT)
-            .sum()
+            .filter(|&x| x <= self.size_limit)
+            .sum())
     }
 
-    fn run_part_2(&mut self, data: &Self::Input) -> Self::Part2Result {
-        const TOTAL_SIZE: usize = 70_000_000;
-        const SIZE_REQUIREMENT: usize = 30_000_000;
-
+    fn run_part_2(&mut self, data: &Self::Input) -> Result<Self::Part2Result, SolutionError> {
         let tree = Tree::build_from_commands(data);
         let result = walk_dir_sizes(&tree);
-        let available_space = TOTAL_SIZE - result.root_size;
-        let size_to_free = SIZE_REQUIREMENT - available_space;
+        let available_space = self
+            .total_size
+            .checked_sub(result.root_size)
+            .ok_or_else(|| {
+                SolutionError::invalid_input(format!(
+                    "the files take {} but the disk only holds {}",
+                    result.root_size, self.total_size
+                ))
+            })?;
+        let size_to_free = self.size_requirement.saturating_sub(available_space);
+
+        if size_to_free == 0 {
+            return Ok(0);
+        }
 
         result
             .dir_sizes
             .into_iter()
             .filter(|&x| x >= size_to_free)
             .min()
-            .unwrap()
+            .ok_or_else(|| {
+                SolutionError::no_solution(format!(
+                    "no directory is large enough to free {size_to_free}"
+                ))
+            })
     }
 }
 
@@ -209,13 +237,16 @@ pub enum DirectoryEntry {
 }
 
 fn command(input: &str) -> IResult<&str, Command> {
-    map(
-        separated_pair(
-            char('$'),
-            multispace1,
-            alt((change_directory_command, list_command)),
+    context(
+        "command",
+        map(
+            separated_pair(
+                char('$'),
+                multispace1,
+                alt((change_directory_command, list_command)),
+            ),
+            |x| x.1,
         ),
-        |x| x.1,
     )(input)
 }
 
@@ -253,7 +284,7 @@ fn parent_directory(input: &str) -> IResult<&str, Directory> {
 }
 
 fn entry(input: &str) -> IResult<&str, DirectoryEntry> {
-    alt((file_entry, directory_entry))(input)
+    context("directory entry", alt((file_entry, directory_entry)))(input)
 }
 
 fn file_entry(input: &str) -> IResult<&str, DirectoryEntry> {
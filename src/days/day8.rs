@@ -1,12 +1,10 @@
-use crate::{parsing::line_separated, Solution};
-use itertools::{FoldWhile, Itertools};
-use nom::{
-    character::complete::satisfy,
-    combinator::{all_consuming, map},
-    multi::many1,
+use crate::{
+    grid::{char_grid, Direction, Grid},
+    parsing::{ParseError, ParseFinish},
+    Solution, SolutionError,
 };
-use std::fmt::Display;
-use std::ops::{Index, IndexMut};
+use itertools::{FoldWhile, Itertools};
+use nom::{character::complete::satisfy, combinator::map, error::context};
 
 #[derive(Default)]
 pub struct Day8 {}
@@ -17,39 +15,37 @@ impl Solution for Day8 {
 
     type Input = Grid<Height>;
 
-    fn parse<'a>(
-        &mut self,
-        input: &'a str,
-    ) -> Result<Self::Input, nom::Err<nom::error::Error<&'a str>>> {
-        map(
-            all_consuming(line_separated(many1(map(
-                satisfy(|x| x.is_ascii_digit()),
-                |x| x.to_digit(10).unwrap().try_into().unwrap(),
-            )))),
-            |g| Grid { rows: g },
-        )(input)
-        .map(|x| x.1)
-    }
-
-    fn run_part_1(&mut self, data: &Self::Input) -> Self::Part1Result {
-        data.iter()
-            .filter(|&(coords, height)| {
-                DIRECTIONS.iter().any(|&direction| {
-                    let maximum = data.directional_iter(coords, direction).max();
-                    maximum.map_or(true, |h| h < height)
+    fn parse(&mut self, input: &str) -> Result<Self::Input, ParseError> {
+        char_grid(context(
+            "tree height",
+            map(satisfy(|x| x.is_ascii_digit()), |x| {
+                x.to_digit(10).unwrap().try_into().unwrap()
+            }),
+        ))(input)
+        .finish_parse(input)
+    }
+
+    fn run_part_1(&mut self, data: &Self::Input) -> Result<Self::Part1Result, SolutionError> {
+        Ok(data
+            .iter()
+            .filter(|&(position, height)| {
+                Direction::ALL.into_iter().any(|direction| {
+                    let maximum = data.ray(position, direction).map(|x| x.1).max();
+                    maximum.is_none_or(|h| h < height)
                 })
             })
-            .count()
-    }
-
-    fn run_part_2(&mut self, data: &Self::Input) -> Self::Part2Result {
-        data.iter()
-            .map(|(coords, height)| {
-                DIRECTIONS
-                    .iter()
-                    .map(|&direction| {
-                        data.directional_iter(coords, direction)
-                            .fold_while(0_u64, |acc, h| {
+            .count())
+    }
+
+    fn run_part_2(&mut self, data: &Self::Input) -> Result<Self::Part2Result, SolutionError> {
+        Ok(data
+            .iter()
+            .map(|(position, height)| {
+                Direction::ALL
+                    .into_iter()
+                    .map(|direction| {
+                        data.ray(position, direction)
+                            .fold_while(0_u64, |acc, (_, h)| {
                                 if h < height {
                                     FoldWhile::Continue(acc + 1)
                                 } else {
@@ -61,193 +57,72 @@ impl Solution for Day8 {
                     .product()
             })
             .max()
-            .unwrap()
+            .unwrap())
     }
 }
 
-type Height = usize;
-
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
-struct Coordinates {
-    row: usize,
-    column: usize,
-}
-
-pub struct Grid<T> {
-    rows: Vec<Vec<T>>,
-}
-
-struct DirectionalGridIterator<'g, T> {
-    grid: &'g Grid<T>,
-    direction: Direction,
-    coords: Coordinates,
-}
-
-struct WholeGridIterator<'g, T> {
-    grid: &'g Grid<T>,
-    coords: Coordinates,
-}
-
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-enum Direction {
-    Up,
-    Left,
-    Down,
-    Right,
-}
-
-const DIRECTIONS: [Direction; 4] = [
-    Direction::Up,
-    Direction::Left,
-    Direction::Down,
-    Direction::Right,
-];
-
-impl Coordinates {
-    fn within_grid<T>(&self, grid: &Grid<T>) -> bool {
-        self.row < grid.row_len() && self.column < grid.column_len()
-    }
-
-    fn mov(&self, direction: Direction) -> Self {
-        match direction {
-            Direction::Up => self.up(),
-            Direction::Left => self.left(),
-            Direction::Right => self.right(),
-            Direction::Down => self.down(),
-        }
-    }
-
-    fn up(&self) -> Self {
-        Coordinates {
-            row: self.row.wrapping_sub(1),
-            column: self.column,
-        }
-    }
-
-    fn down(&self) -> Self {
-        Coordinates {
-            row: self.row + 1,
-            column: self.column,
-        }
-    }
-
-    fn left(&self) -> Self {
-        Coordinates {
-            row: self.row,
-            column: self.column.wrapping_sub(1),
-        }
-    }
-
-    fn right(&self) -> Self {
-        Coordinates {
-            row: self.row,
-            column: self.column + 1,
-        }
-    }
-}
-
-impl<T> Grid<T> {
-    fn row_len(&self) -> usize {
-        self.rows.len()
-    }
+/// Computes the same answers in a single pass over every line of the grid in
+/// each direction, instead of walking from every tree to the edge.
+#[derive(Default)]
+pub struct Day8MonotonicStack {}
 
-    fn column_len(&self) -> usize {
-        self.rows[0].len()
-    }
+impl Solution for Day8MonotonicStack {
+    type Part1Result = usize;
+    type Part2Result = u64;
 
-    fn iter(&self) -> WholeGridIterator<T> {
-        WholeGridIterator::new(self)
-    }
+    type Input = Grid<Height>;
 
-    fn directional_iter(
-        &self,
-        origin: Coordinates,
-        direction: Direction,
-    ) -> DirectionalGridIterator<T> {
-        DirectionalGridIterator::new(self, origin, direction)
+    fn parse(&mut self, input: &str) -> Result<Self::Input, ParseError> {
+        Day8 {}.parse(input)
     }
-}
 
-impl<'g, T> WholeGridIterator<'g, T> {
-    fn new(grid: &'g Grid<T>) -> Self {
-        Self {
-            grid,
-            coords: Coordinates { row: 0, column: 0 },
-        }
-    }
+    fn run_part_1(&mut self, data: &Self::Input) -> Result<Self::Part1Result, SolutionError> {
+        let mut visible = data.map(|_| false);
 
-    fn advance(&mut self) {
-        if self.coords.column < self.grid.column_len() - 1 {
-            self.coords.column += 1;
-        } else {
-            self.coords.row += 1;
-            self.coords.column = 0;
-        }
-    }
-}
+        for direction in Direction::ALL {
+            // Each tree looks in `direction` towards the ones before it.
+            for line in data.lines(direction.opposite()) {
+                let mut maximum = None;
 
-impl<'g, T> Iterator for WholeGridIterator<'g, T> {
-    type Item = (Coordinates, &'g T);
+                for position in line {
+                    let height = data[position];
 
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.coords.within_grid(self.grid) {
-            let res = Some((self.coords, &self.grid[&self.coords]));
-            self.advance();
-            res
-        } else {
-            None
+                    if maximum.is_none_or(|h| h < height) {
+                        visible[position] = true;
+                        maximum = Some(height);
+                    }
+                }
+            }
         }
-    }
-}
 
-impl<'g, T> DirectionalGridIterator<'g, T> {
-    fn new(grid: &'g Grid<T>, origin: Coordinates, direction: Direction) -> Self {
-        let coords = origin.mov(direction);
-        Self {
-            grid,
-            coords,
-            direction,
-        }
+        Ok(visible.iter().filter(|&(_, &x)| x).count())
     }
-}
 
-impl<'g, T> Iterator for DirectionalGridIterator<'g, T> {
-    type Item = &'g T;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.coords.within_grid(self.grid) {
-            let res = Some(&self.grid[&self.coords]);
-            self.coords = self.coords.mov(self.direction);
-            res
-        } else {
-            None
-        }
-    }
-}
+    fn run_part_2(&mut self, data: &Self::Input) -> Result<Self::Part2Result, SolutionError> {
+        let mut scores = data.map(|_| 1_u64);
 
-impl<T> Index<&Coordinates> for Grid<T> {
-    type Output = T;
+        for direction in Direction::ALL {
+            for line in data.lines(direction.opposite()) {
+                // Trees seen so far that are not hidden behind a later tree at
+                // least as high, so their heights are decreasing.
+                let mut stack: Vec<(usize, Height)> = vec![];
 
-    fn index(&self, index: &Coordinates) -> &Self::Output {
-        &self.rows[index.row][index.column]
-    }
-}
+                for (i, position) in line.into_iter().enumerate() {
+                    let height = data[position];
 
-impl<T> IndexMut<&Coordinates> for Grid<T> {
-    fn index_mut(&mut self, index: &Coordinates) -> &mut Self::Output {
-        &mut self.rows[index.row][index.column]
-    }
-}
+                    while stack.last().is_some_and(|&(_, h)| h < height) {
+                        stack.pop();
+                    }
 
-impl<T: Display> Display for Grid<T> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for row in self.rows.iter() {
-            for column in row.iter() {
-                write!(f, "{column}")?;
+                    let distance = stack.last().map_or(i, |&(j, _)| i - j);
+                    scores[position] *= distance as u64;
+                    stack.push((i, height));
+                }
             }
-            writeln!(f)?;
         }
 
-        Ok(())
+        Ok(scores.iter().map(|(_, &x)| x).max().unwrap())
     }
 }
+
+type Height = usize;
@@ -7,18 +7,29 @@ use std::{
 use nom::{
     branch::alt,
     character::complete::{char, space0},
-    combinator::{all_consuming, map},
+    combinator::map,
+    error::context,
     sequence::separated_pair,
-    IResult,
 };
 
 use crate::{
-    parsing::{integer, line_separated},
-    Solution,
+    parsing::{all_lines, integer, IResult, ParseError, ParseFinish},
+    ParamError, Params, Solution, SolutionError,
 };
 
-#[derive(Default)]
-pub struct Day9 {}
+pub struct Day9 {
+    part_1_knots: usize,
+    part_2_knots: usize,
+}
+
+impl Default for Day9 {
+    fn default() -> Self {
+        Self {
+            part_1_knots: 2,
+            part_2_knots: 10,
+        }
+    }
+}
 
 impl Solution for Day9 {
     type Part1Result = usize;
@@ -26,21 +37,29 @@ impl Solution for Day9 {
 
     type Input = Vec<Move>;
 
-    fn parse<'a>(
-        &mut self,
-        input: &'a str,
-    ) -> Result<Self::Input, nom::Err<nom::error::Error<&'a str>>> {
-        all_consuming(line_separated(parse_move))(input).map(|x| x.1)
+    fn configure(&mut self, params: &mut Params) -> Result<(), ParamError> {
+        params.apply("part_1_knots", &mut self.part_1_knots)?;
+        params.apply("part_2_knots", &mut self.part_2_knots)?;
+
+        if self.part_1_knots == 0 || self.part_2_knots == 0 {
+            return Err(ParamError::new("a rope needs at least one knot"));
+        }
+
+        Ok(())
     }
 
-    fn run_part_1(&mut self, data: &Self::Input) -> Self::Part1Result {
-        let rope = Rope::new(2);
-        track_tail(rope, data)
+    fn parse(&mut self, input: &str) -> Result<Self::Input, ParseError> {
+        all_lines(parse_move)(input).finish_parse(input)
     }
 
-    fn run_part_2(&mut self, data: &Self::Input) -> Self::Part2Result {
-        let rope = Rope::new(10);
-        track_tail(rope, data)
+    fn run_part_1(&mut self, data: &Self::Input) -> Result<Self::Part1Result, SolutionError> {
+        let rope = Rope::new(self.part_1_knots);
+        Ok(track_tail(rope, data))
+    }
+
+    fn run_part_2(&mut self, data: &Self::Input) -> Result<Self::Part2Result, SolutionError> {
+        let rope = Rope::new(self.part_2_knots);
+        Ok(track_tail(rope, data))
     }
 }
 
@@ -85,9 +104,7 @@ struct Coordinates {
 impl Rope {
     fn new(size: usize) -> Self {
         Self {
-            parts: std::iter::repeat(Coordinates { x: 0, y: 0 })
-                .take(size)
-                .collect(),
+            parts: std::iter::repeat_n(Coordinates { x: 0, y: 0 }, size).collect(),
         }
     }
 
@@ -187,10 +204,13 @@ fn parse_move(input: &str) -> IResult<&str, Move> {
 }
 
 fn parse_direction(input: &str) -> IResult<&str, Direction> {
-    alt((
-        map(char('U'), |_| Direction::Up),
-        map(char('L'), |_| Direction::Left),
-        map(char('D'), |_| Direction::Down),
-        map(char('R'), |_| Direction::Right),
-    ))(input)
+    context(
+        "direction",
+        alt((
+            map(char('U'), |_| Direction::Up),
+            map(char('L'), |_| Direction::Left),
+            map(char('D'), |_| Direction::Down),
+            map(char('R'), |_| Direction::Right),
+        )),
+    )(input)
 }
@@ -0,0 +1,96 @@
+use crate::{ParamError, ParseError};
+use std::fmt::{self, Display};
+
+/// A failure of a solution on input that parsed fine but does not satisfy
+/// the assumptions of the puzzle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolutionError {
+    /// The input violates a property the solution relies on, e.g. a value
+    /// that must be present is missing.
+    InvalidInput(String),
+    /// The input is well-formed, but the search finished without an answer.
+    NoSolution(String),
+    /// The solution stopped early because its
+    /// [`CancellationToken`](crate::CancellationToken) was cancelled.
+    Cancelled,
+    /// The part has not been written yet, e.g. in a freshly scaffolded day.
+    NotImplemented,
+    /// The puzzle of the day has no such part, like part 2 of the last day.
+    NoSuchPart,
+}
+
+impl SolutionError {
+    pub fn invalid_input<D: Display>(message: D) -> Self {
+        Self::InvalidInput(message.to_string())
+    }
+
+    pub fn no_solution<D: Display>(message: D) -> Self {
+        Self::NoSolution(message.to_string())
+    }
+}
+
+impl Display for SolutionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolutionError::InvalidInput(message) => write!(f, "invalid input: {message}"),
+            SolutionError::NoSolution(message) => write!(f, "no solution: {message}"),
+            SolutionError::Cancelled => write!(f, "cancelled"),
+            SolutionError::NotImplemented => write!(f, "not implemented"),
+            SolutionError::NoSuchPart => write!(f, "the puzzle has no such part"),
+        }
+    }
+}
+
+impl std::error::Error for SolutionError {}
+
+/// Any failure of [`solve`](crate::solve).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    UnknownDay(u8),
+    /// Only parts 1 and 2 exist.
+    UnknownPart(u8),
+    Params(ParamError),
+    Parse(ParseError),
+    Solution(SolutionError),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::UnknownDay(day) => write!(f, "day {day} is not registered"),
+            Error::UnknownPart(part) => write!(f, "part {part} does not exist"),
+            Error::Params(e) => write!(f, "invalid parameters: {e}"),
+            Error::Parse(e) => write!(f, "error parsing input: {e}"),
+            Error::Solution(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::UnknownDay(_) | Error::UnknownPart(_) => None,
+            Error::Params(e) => Some(e),
+            Error::Parse(e) => Some(e),
+            Error::Solution(e) => Some(e),
+        }
+    }
+}
+
+impl From<ParamError> for Error {
+    fn from(e: ParamError) -> Self {
+        Error::Params(e)
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(e: ParseError) -> Self {
+        Error::Parse(e)
+    }
+}
+
+impl From<SolutionError> for Error {
+    fn from(e: SolutionError) -> Self {
+        Error::Solution(e)
+    }
+}
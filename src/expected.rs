@@ -0,0 +1,179 @@
+use std::{
+    fmt::{self, Display},
+    path::{Path, PathBuf},
+};
+
+/// Known answers for a single input file, stored next to it in a sidecar file
+/// with the `expected` extension, e.g. `example1.txt` and `example1.expected`.
+///
+/// The sidecar consists of `[part1]` and `[part2]` sections, each followed by
+/// the answer exactly as the solution displays it. Answers can span multiple
+/// lines, which is needed for puzzles that render their result. Either section
+/// may be omitted if the answer is not known.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExpectedAnswers {
+    part_1: Option<String>,
+    part_2: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail(Diff),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diff {
+    lines: Vec<DiffLine>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum DiffLine {
+    Same(String),
+    Expected(String),
+    Actual(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExpectedAnswersError {
+    line: usize,
+    message: String,
+}
+
+const PART_1_HEADER: &str = "[part1]";
+const PART_2_HEADER: &str = "[part2]";
+
+impl ExpectedAnswers {
+    pub fn sidecar_path(input_path: &Path) -> PathBuf {
+        input_path.with_extension("expected")
+    }
+
+    pub fn parse(contents: &str) -> Result<Self, ExpectedAnswersError> {
+        let mut sections: [Option<String>; 2] = [None, None];
+        let mut current = None;
+
+        for (i, line) in contents.lines().enumerate() {
+            let header = match line.trim_end() {
+                PART_1_HEADER => Some(0),
+                PART_2_HEADER => Some(1),
+                _ => None,
+            };
+
+            if let Some(idx) = header {
+                if sections[idx].is_some() {
+                    return Err(ExpectedAnswersError {
+                        line: i + 1,
+                        message: format!("duplicate section {}", line.trim_end()),
+                    });
+                }
+                sections[idx] = Some(String::new());
+                current = Some(idx);
+            } else if let Some(idx) = current {
+                let answer = sections[idx].as_mut().unwrap();
+                answer.push_str(line);
+                answer.push('\n');
+            } else if !line.trim().is_empty() {
+                return Err(ExpectedAnswersError {
+                    line: i + 1,
+                    message: format!(
+                        "expected {PART_1_HEADER} or {PART_2_HEADER} before the answer"
+                    ),
+                });
+            }
+        }
+
+        let [part_1, part_2] = sections;
+
+        Ok(Self {
+            part_1: part_1.map(|x| normalize(&x)),
+            part_2: part_2.map(|x| normalize(&x)),
+        })
+    }
+
+    pub fn part_1(&self) -> Option<&str> {
+        self.part_1.as_deref()
+    }
+
+    pub fn part_2(&self) -> Option<&str> {
+        self.part_2.as_deref()
+    }
+}
+
+/// Compares an answer against the expected one, ignoring trailing whitespace
+/// on every line as well as trailing empty lines.
+pub fn check(expected: &str, actual: &str) -> Verdict {
+    let expected = normalize(expected);
+    let actual = normalize(actual);
+
+    if expected == actual {
+        return Verdict::Pass;
+    }
+
+    let mut expected_lines = expected.lines();
+    let mut actual_lines = actual.lines();
+    let mut lines = vec![];
+
+    loop {
+        match (expected_lines.next(), actual_lines.next()) {
+            (None, None) => break,
+            (Some(e), Some(a)) if e == a => lines.push(DiffLine::Same(e.to_owned())),
+            (e, a) => {
+                lines.extend(e.map(|x| DiffLine::Expected(x.to_owned())));
+                lines.extend(a.map(|x| DiffLine::Actual(x.to_owned())));
+            }
+        }
+    }
+
+    Verdict::Fail(Diff { lines })
+}
+
+fn normalize(answer: &str) -> String {
+    answer
+        .lines()
+        .map(str::trim_end)
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim_end()
+        .to_owned()
+}
+
+impl Verdict {
+    pub fn is_pass(&self) -> bool {
+        *self == Verdict::Pass
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "PASS"),
+            Verdict::Fail(diff) => write!(f, "FAIL\n{diff}"),
+        }
+    }
+}
+
+impl Display for Diff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, line) in self.lines.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+
+            match line {
+                DiffLine::Same(x) => write!(f, "  {x}")?,
+                DiffLine::Expected(x) => write!(f, "- {x}")?,
+                DiffLine::Actual(x) => write!(f, "+ {x}")?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for ExpectedAnswersError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for ExpectedAnswersError {}
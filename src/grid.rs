@@ -0,0 +1,240 @@
+use crate::parsing::{all_lines, IResult};
+use nom::{combinator::map_opt, error::context, error::VerboseError, multi::many1, Parser};
+use std::{
+    fmt::{self, Display},
+    ops::{Index, IndexMut},
+};
+
+/// A rectangular grid of cells, addressed by column `x` and row `y` from the
+/// top left corner.
+///
+/// Moving off an edge gives no position rather than one that wrapped around,
+/// so walking the grid never needs to check for underflow.
+///
+/// ```
+/// use advent_of_code_2022::grid::{Direction, Grid, Position};
+///
+/// let grid = Grid::from_rows(vec![vec![1, 2, 3], vec![4, 5, 6]]).unwrap();
+/// let ray: Vec<_> = grid
+///     .ray(Position { x: 2, y: 1 }, Direction::Left)
+///     .map(|(_, &x)| x)
+///     .collect();
+/// assert_eq!(ray, [5, 4]);
+/// assert_eq!(grid.to_string(), "123\n456\n");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    /// Cells row by row.
+    cells: Vec<T>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Position {
+    pub x: usize,
+    pub y: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Left,
+    Down,
+    Right,
+}
+
+impl<T> Grid<T> {
+    /// Builds a grid from its rows, which must all have the same, non-zero
+    /// length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Self> {
+        let width = rows.first()?.len();
+        let height = rows.len();
+
+        if width == 0 || rows.iter().any(|x| x.len() != width) {
+            return None;
+        }
+
+        Some(Self {
+            width,
+            height,
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, position: Position) -> bool {
+        position.x < self.width && position.y < self.height
+    }
+
+    pub fn get(&self, position: Position) -> Option<&T> {
+        self.contains(position)
+            .then(|| &self.cells[position.y * self.width + position.x])
+    }
+
+    /// A grid of the same size with `f` applied to every cell.
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Every cell with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> + '_ {
+        self.cells.iter().enumerate().map(|(i, cell)| {
+            let position = Position {
+                x: i % self.width,
+                y: i / self.width,
+            };
+            (position, cell)
+        })
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        self.cells.chunks(self.width)
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> + '_ {
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    /// The position one step away in `direction`, if it is on the grid.
+    pub fn step(&self, position: Position, direction: Direction) -> Option<Position> {
+        let (dx, dy) = direction.offset();
+        self.offset(position, dx, dy)
+    }
+
+    fn offset(&self, position: Position, dx: isize, dy: isize) -> Option<Position> {
+        let position = Position {
+            x: position.x.checked_add_signed(dx)?,
+            y: position.y.checked_add_signed(dy)?,
+        };
+
+        self.contains(position).then_some(position)
+    }
+
+    /// The positions on the grid that share an edge with `position`.
+    pub fn neighbours(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |x| self.step(position, x))
+    }
+
+    /// The positions on the grid that share an edge or a corner with
+    /// `position`.
+    pub fn surrounding(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        (-1..=1)
+            .flat_map(|dy| (-1..=1).map(move |dx| (dx, dy)))
+            .filter(|&offset| offset != (0, 0))
+            .filter_map(move |(dx, dy)| self.offset(position, dx, dy))
+    }
+
+    /// The cells from `origin`, which is left out, to the edge in
+    /// `direction`.
+    pub fn ray(
+        &self,
+        origin: Position,
+        direction: Direction,
+    ) -> impl Iterator<Item = (Position, &T)> + '_ {
+        std::iter::successors(self.step(origin, direction), move |&x| {
+            self.step(x, direction)
+        })
+        .map(|x| (x, &self[x]))
+    }
+
+    /// Every row or column, each as the positions from one edge to the other
+    /// going in `direction`, e.g. rows from right to left for `Left`.
+    pub fn lines(&self, direction: Direction) -> Vec<Vec<Position>> {
+        let (width, height) = (self.width, self.height);
+        let row = |y| (0..width).map(move |x| Position { x, y });
+        let column = |x| (0..height).map(move |y| Position { x, y });
+
+        match direction {
+            Direction::Right => (0..height).map(|y| row(y).collect()).collect(),
+            Direction::Left => (0..height).map(|y| row(y).rev().collect()).collect(),
+            Direction::Down => (0..width).map(|x| column(x).collect()).collect(),
+            Direction::Up => (0..width).map(|x| column(x).rev().collect()).collect(),
+        }
+    }
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Left,
+        Direction::Down,
+        Direction::Right,
+    ];
+
+    pub fn opposite(self) -> Self {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Left => Direction::Right,
+            Direction::Down => Direction::Up,
+            Direction::Right => Direction::Left,
+        }
+    }
+
+    fn offset(self) -> (isize, isize) {
+        match self {
+            Direction::Up => (0, -1),
+            Direction::Left => (-1, 0),
+            Direction::Down => (0, 1),
+            Direction::Right => (1, 0),
+        }
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, index: Position) -> &Self::Output {
+        assert!(self.contains(index), "{index:?} is outside the grid");
+        &self.cells[index.y * self.width + index.x]
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, index: Position) -> &mut Self::Output {
+        assert!(self.contains(index), "{index:?} is outside the grid");
+        &mut self.cells[index.y * self.width + index.x]
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Parses the whole input as a grid with a line per row and a character per
+/// cell, each parsed by `cell`.
+pub fn char_grid<'a, T, F>(cell: F) -> impl FnMut(&'a str) -> IResult<&'a str, Grid<T>>
+where
+    F: Parser<&'a str, T, VerboseError<&'a str>>,
+{
+    context(
+        "rectangular grid",
+        map_opt(all_lines(many1(cell)), Grid::from_rows),
+    )
+}
@@ -1,23 +1,75 @@
-#![feature(is_some_and)]
-#![feature(map_try_insert)]
+mod answer;
+pub mod bench;
+mod cancellation;
 mod days;
+mod error;
+pub mod expected;
+pub mod grid;
 pub(crate) mod iterators;
+pub mod params;
 pub(crate) mod parsing;
-use std::fmt::Display;
-
+pub mod registry;
+pub mod snafu;
+pub use answer::Answer;
+pub use cancellation::{CancelTimer, CancellationToken};
 pub use days::*;
+pub use error::{Error, SolutionError};
+pub use params::{ParamError, Params};
+pub use parsing::ParseError;
 
 pub trait Solution: Default {
     type Input;
-    type Part1Result: Display;
-    type Part2Result: Display;
+    type Part1Result: Into<Answer>;
+    type Part2Result: Into<Answer>;
+
+    /// Overrides the defaults of the solution with the parameters it knows,
+    /// removing them from `params`.
+    fn configure(&mut self, _params: &mut Params) -> Result<(), ParamError> {
+        Ok(())
+    }
+
+    /// Hands over the token that asks the solution to stop early. Solutions
+    /// that can run for a long time keep it and check it in their hot loops.
+    fn set_cancellation(&mut self, _token: CancellationToken) {}
+
+    fn parse(&mut self, input: &str) -> Result<Self::Input, ParseError>;
+
+    fn run_part_1(&mut self, data: &Self::Input) -> Result<Self::Part1Result, SolutionError>;
+
+    fn run_part_2(&mut self, data: &Self::Input) -> Result<Self::Part2Result, SolutionError>;
+}
+
+/// Solves one part of a day on the given input with the default parameters.
+///
+/// ```
+/// use advent_of_code_2022::{solve, Answer};
+///
+/// let answer = solve(1, 1, "1000\n2000\n\n4000\n").unwrap();
+/// assert_eq!(answer, Answer::Integer(4000));
+/// ```
+pub fn solve(day: u8, part: u8, input: &str) -> Result<Answer, Error> {
+    solve_with_params(day, part, input, &Params::default())
+}
+
+/// Solves one part of a day on the given input, overriding the defaults of
+/// the solution with `params`, e.g. the smaller search area of an example.
+pub fn solve_with_params(day: u8, part: u8, input: &str, params: &Params) -> Result<Answer, Error> {
+    let registration = registry::get(day).ok_or(Error::UnknownDay(day))?;
+
+    if !(1..=2).contains(&part) {
+        return Err(Error::UnknownPart(part));
+    }
 
-    fn parse<'a>(
-        &mut self,
-        input: &'a str,
-    ) -> Result<Self::Input, nom::Err<nom::error::Error<&'a str>>>;
+    let mut solution = registration.create();
+    let mut params = params.clone();
+    solution.configure(&mut params)?;
+    params.ensure_all_applied()?;
 
-    fn run_part_1(&mut self, data: &Self::Input) -> Self::Part1Result;
+    let data = solution.parse(input)?;
+    let result = match part {
+        1 => solution.run_part_1(data.as_ref())?,
+        _ => solution.run_part_2(data.as_ref())?,
+    };
 
-    fn run_part_2(&mut self, data: &Self::Input) -> Self::Part2Result;
+    Ok(result)
 }
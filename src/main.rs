@@ -1,15 +1,178 @@
-#![feature(is_some_and)]
-use advent_of_code_2022::*;
-use clap::{Parser, ValueEnum};
-use color_eyre::eyre::{Context, Result};
-use std::time::Instant;
+mod cli;
+
+use advent_of_code_2022::{
+    bench::{self, Stats},
+    expected::{self, ExpectedAnswers},
+    params,
+    registry::{self, DynSolution, Registration},
+    Answer, CancellationToken, ParamError, Params, SolutionError,
+};
+use clap::{ArgAction, Parser, Subcommand, ValueEnum};
+#[cfg(feature = "server")]
+use cli::server;
+use cli::{
+    history,
+    input::InputSource,
+    job::{self, JobOutput},
+    memory,
+    output::{self, OutputFormat},
+    report::{self, CrossCheck, FileReport, PartReport, Status},
+    scaffold,
+    watch::Watcher,
+};
+use color_eyre::eyre::{eyre, Context, Result};
+use std::{
+    any::Any,
+    io::{self, IsTerminal},
+    ops::{ControlFlow, RangeInclusive},
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+use tracing::{info, info_span, level_filters::LevelFilter, warn};
+use tracing_subscriber::fmt::format::FmtSpan;
+
+const ALL_DAYS: RangeInclusive<u8> = 1..=25;
+const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
 
 #[derive(Parser, Debug)]
+#[clap(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 struct Args {
-    #[clap(short, long, value_parser = clap::value_parser!(u8).range(1..26))]
-    day: u8,
-    #[clap(short, long, value_enum)]
-    part: Part,
+    #[clap(subcommand)]
+    command: Option<Command>,
+    /// Day to run, or an inclusive range of days, e.g. `1-21`.
+    #[clap(short, long, value_parser = parse_days, required_unless_present = "all")]
+    day: Option<RangeInclusive<u8>>,
+    /// Run every day that has an input directory.
+    #[clap(long, conflicts_with = "day")]
+    all: bool,
+    /// Input file, directory of `*.txt` inputs, or `-` for stdin, instead of
+    /// the day's input directory. Requires a single day.
+    #[clap(short, long, value_name = "PATH", conflicts_with = "all")]
+    input: Option<PathBuf>,
+    /// Directory that contains the `dayN` input directories.
+    #[clap(long, value_name = "DIR", default_value = "./input")]
+    input_root: PathBuf,
+    #[clap(short, long, value_enum, required = true)]
+    part: Option<Part>,
+    /// Time parsing and each part over N iterations, after N/10 warm-up runs.
+    #[clap(long, value_name = "N", value_parser = clap::value_parser!(u32).range(1..))]
+    bench: Option<u32>,
+    /// Stop a part that runs longer than this, e.g. `30s` or `500ms`, report
+    /// it as TIMEOUT and continue with the next one.
+    #[clap(long, value_name = "DURATION", value_parser = parse_duration)]
+    timeout: Option<Duration>,
+    /// Overrides a parameter of the solution, e.g. `target_y=10`, or of one
+    /// of the days when running several, e.g. `15:target_y=10`. Takes
+    /// precedence over the `.params` file next to the input.
+    #[clap(long = "param", value_name = "[DAY:]KEY=VALUE", value_parser = parse_param)]
+    params: Vec<ParamOverride>,
+    /// Number of input files to run at the same time. Their output is still
+    /// printed in order.
+    #[clap(short, long, value_name = "N", default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+    jobs: u32,
+    /// Re-run whenever a file in the input directory changes, e.g. an example
+    /// or its expected answers.
+    #[clap(short, long)]
+    watch: bool,
+    /// Implementation of the days to run, if they have several.
+    #[clap(long, value_name = "NAME", default_value = registry::DEFAULT_VARIANT)]
+    variant: String,
+    /// Also run every other variant of the days on the same inputs, and fail
+    /// if their answers differ.
+    #[clap(long)]
+    cross_check: bool,
+    /// Format of the results written to stdout.
+    #[clap(long, value_enum, default_value = "text")]
+    output: OutputFormat,
+    /// JSON-lines file that every run appends its answers and timings to.
+    #[clap(long, value_name = "PATH", default_value = history::DEFAULT_PATH)]
+    history: PathBuf,
+    /// Name of this run in the history, to compare against it later.
+    #[clap(long, value_name = "NAME")]
+    label: Option<String>,
+    /// Log diagnostics to stderr: `-v` for the progress and timings of each
+    /// part, `-vv` for what happens inside the solutions, `-vvv` for
+    /// everything.
+    #[clap(short, long, action = ArgAction::Count, global = true)]
+    verbose: u8,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Generate, declare and register the module of a new day, and create its
+    /// input directory.
+    Scaffold {
+        #[clap(short, long, value_parser = parse_day)]
+        day: u8,
+        /// Title of the puzzle, shown when listing days.
+        #[clap(short, long)]
+        name: Option<String>,
+        /// Directory that contains `days.rs`, `registry.rs` and
+        /// `registry/variants.rs`.
+        #[clap(long, value_name = "DIR", default_value = "./src")]
+        source_root: PathBuf,
+        /// Directory that contains the `dayN` input directories.
+        #[clap(long, value_name = "DIR", default_value = "./input")]
+        input_root: PathBuf,
+    },
+    /// Compare the timings and answers of every part of the latest run in the
+    /// history with its latest earlier run, or with a labelled run.
+    Compare {
+        /// Label of the run to compare against, instead of the latest earlier
+        /// run of each part.
+        #[clap(short, long, value_name = "NAME")]
+        baseline: Option<String>,
+        /// Flag parts that got slower by more than this many percent.
+        #[clap(short, long, value_name = "PERCENT", default_value_t = 10.0)]
+        threshold: f64,
+        #[clap(long, value_name = "PATH", default_value = history::DEFAULT_PATH)]
+        history: PathBuf,
+    },
+    /// Serve the solutions over HTTP, taking the input of a part in the body
+    /// of `POST /solve/{day}/{part}` and answering with JSON.
+    /// Pass `-v` to log the address it listens on and every request.
+    #[cfg(feature = "server")]
+    Serve {
+        /// Address to listen on. Only local clients can connect by default.
+        #[clap(short, long, default_value = "127.0.0.1:8022")]
+        address: String,
+        /// Number of requests to handle at the same time, one per CPU by
+        /// default. Further requests wait for one of them to finish.
+        #[clap(short, long, value_name = "N", value_parser = clap::value_parser!(u32).range(1..))]
+        jobs: Option<u32>,
+        /// Stop a part that runs longer than this and answer with an error.
+        #[clap(long, value_name = "DURATION", value_parser = parse_duration)]
+        timeout: Option<Duration>,
+    },
+}
+
+/// A `--param`, which only applies to `day` if it is scoped to one.
+#[derive(Debug, Clone)]
+struct ParamOverride {
+    day: Option<u8>,
+    key: String,
+    value: String,
+}
+
+impl Args {
+    fn is_text(&self) -> bool {
+        self.output == OutputFormat::Text
+    }
+
+    /// The `--param` overrides that apply to `day`.
+    fn params_for(&self, day: u8) -> Params {
+        let mut params = Params::default();
+
+        for x in self
+            .params
+            .iter()
+            .filter(|x| x.day.is_none_or(|d| d == day))
+        {
+            params.insert(x.key.as_str(), x.value.as_str());
+        }
+
+        params
+    }
 }
 
 #[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
@@ -22,100 +185,608 @@ enum Part {
 fn main() -> Result<()> {
     color_eyre::install()?;
     let args = Args::parse();
+    init_logging(args.verbose);
+
+    match &args.command {
+        Some(Command::Scaffold {
+            day,
+            name,
+            source_root,
+            input_root,
+        }) => {
+            let name = name.clone().unwrap_or_else(|| format!("Day {day}"));
+            return scaffold::scaffold(*day, &name, source_root, input_root);
+        }
+        Some(Command::Compare {
+            baseline,
+            threshold,
+            history,
+        }) => {
+            let runs = history::load(history)?;
+            return history::compare(&runs, baseline.as_deref(), *threshold);
+        }
+        #[cfg(feature = "server")]
+        Some(Command::Serve {
+            address,
+            jobs,
+            timeout,
+        }) => {
+            return server::serve(address, jobs.map(|x| x as usize), *timeout);
+        }
+        None => (),
+    }
+
+    let registrations: Vec<_> = if args.all {
+        registry::all().iter().collect()
+    } else {
+        let days = args.day.clone().unwrap();
+        days.map(|day| registry::get(day).ok_or_else(|| eyre!("day {day} is not registered")))
+            .collect::<Result<_>>()?
+    };
+
+    for registration in &registrations {
+        if registration.create_variant(&args.variant).is_none() {
+            let names: Vec<_> = registration.variant_names().collect();
+            return Err(eyre!(
+                "day {} has no variant '{}', expected one of: {}",
+                registration.day,
+                args.variant,
+                names.join(", ")
+            ));
+        }
+    }
+
+    if args.input.is_some() && registrations.len() > 1 {
+        return Err(eyre!("--input can only be used with a single day"));
+    }
+
+    for param in &args.params {
+        match param.day {
+            None if registrations.len() > 1 => {
+                return Err(eyre!(
+                    "--param {}={} would apply to every day, scope it to one, e.g. `{}:{0}={1}`",
+                    param.key,
+                    param.value,
+                    registrations[0].day
+                ));
+            }
+            Some(day) if registrations.iter().all(|x| x.day != day) => {
+                return Err(eyre!(
+                    "--param {day}:{}={} is for day {day}, which is not being run",
+                    param.key,
+                    param.value
+                ));
+            }
+            _ => (),
+        }
+    }
+
+    if cfg!(feature = "count-allocations") && args.jobs > 1 {
+        return Err(eyre!(
+            "--jobs cannot be used when counting allocations, which are shared by all jobs"
+        ));
+    }
+
+    if args.watch {
+        return watch(&args, &registrations);
+    }
 
-    let input_directory = format!("./input/day{}", args.day);
-
-    match args.day {
-        1 => run::<Day1>(args.part, &input_directory),
-        2 => run::<Day2>(args.part, &input_directory),
-        3 => run::<Day3>(args.part, &input_directory),
-        4 => run::<Day4>(args.part, &input_directory),
-        5 => run::<Day5>(args.part, &input_directory),
-        6 => run::<Day6>(args.part, &input_directory),
-        7 => run::<Day7>(args.part, &input_directory),
-        8 => run::<Day8>(args.part, &input_directory),
-        9 => run::<Day9>(args.part, &input_directory),
-        10 => run::<Day10>(args.part, &input_directory),
-        11 => run::<Day11>(args.part, &input_directory),
-        12 => run::<Day12>(args.part, &input_directory),
-        13 => run::<Day13>(args.part, &input_directory),
-        14 => run::<Day14>(args.part, &input_directory),
-        15 => run::<Day15>(args.part, &input_directory),
-        16 => run::<Day16>(args.part, &input_directory),
-        17 => run::<Day17>(args.part, &input_directory),
-        18 => run::<Day18>(args.part, &input_directory),
-        19 => run::<Day19>(args.part, &input_directory),
-        20 => run::<Day20>(args.part, &input_directory),
-        21 => run::<Day21>(args.part, &input_directory),
-        22 => run::<Day22>(args.part, &input_directory),
-        23 => run::<Day23>(args.part, &input_directory),
-        24 => run::<Day24>(args.part, &input_directory),
-        25 => run::<Day25>(args.part, &input_directory),
-        _ => unreachable!(),
-    }?;
+    let reports = run_days(&args, &registrations)?;
+    history::append(
+        &args.history,
+        &history::Run::new(&reports, args.label.clone()),
+    )?;
+    write_reports(&args, &reports, registrations.len() > 1)?;
+    check_reports(&reports)
+}
+
+/// Logs to stderr, so that diagnostics never mix with the results on stdout.
+/// Spans are logged when they close, with the time spent in them.
+fn init_logging(verbose: u8) {
+    let level = match verbose {
+        0 => LevelFilter::WARN,
+        1 => LevelFilter::INFO,
+        2 => LevelFilter::DEBUG,
+        _ => LevelFilter::TRACE,
+    };
+
+    tracing_subscriber::fmt()
+        .with_max_level(level)
+        .with_span_events(FmtSpan::CLOSE)
+        .with_writer(io::stderr)
+        .with_ansi(io::stderr().is_terminal())
+        .init();
+}
+
+fn run_days(args: &Args, registrations: &[&Registration]) -> Result<Vec<FileReport>> {
+    let is_single_day = registrations.len() == 1;
+    let mut jobs = vec![];
+
+    for &registration in registrations {
+        let inputs = match &args.input {
+            Some(path) => InputSource::resolve(path)?,
+            None => {
+                let input_directory = input_directory(args, registration);
+
+                if !is_single_day && !input_directory.is_dir() {
+                    continue;
+                }
+
+                InputSource::directory(&input_directory)?
+            }
+        };
+
+        jobs.extend(inputs.into_iter().map(|source| (registration, source)));
+    }
+
+    let mut reports = Vec::with_capacity(jobs.len());
+    let mut error = None;
+
+    job::run_ordered(
+        &jobs,
+        args.jobs as usize,
+        |(registration, source)| {
+            let mut output = JobOutput::default();
+            let report = run(registration, source, args, &mut output);
+            (output, report)
+        },
+        |(output, report)| {
+            output.print();
+
+            match report {
+                Ok(report) => {
+                    reports.push(report);
+                    ControlFlow::Continue(())
+                }
+                Err(e) => {
+                    error = Some(e);
+                    ControlFlow::Break(())
+                }
+            }
+        },
+    )?;
+
+    match error {
+        Some(e) => Err(e),
+        None => Ok(reports),
+    }
+}
+
+fn input_directory(args: &Args, registration: &Registration) -> PathBuf {
+    args.input_root.join(format!("day{}", registration.day))
+}
+
+/// Writes the records, or the summary table if `summary` is set and the
+/// output is text.
+fn write_reports(args: &Args, reports: &[FileReport], summary: bool) -> Result<()> {
+    if !args.is_text() {
+        output::write_records(args.output, reports, io::stdout().lock())?;
+    } else if summary {
+        report::print_summary(reports);
+    }
 
     Ok(())
 }
 
-fn run<S: Solution>(part: Part, input_directory: &str) -> Result<()> {
-    let mut inputs: Vec<_> = std::fs::read_dir(input_directory)
-        .wrap_err(format!("error reading input directory '{input_directory}'"))?
-        .flatten()
-        .filter(|x| x.file_type().is_ok_and(|t| t.is_file()))
-        .filter(|x| x.path().extension().is_some_and(|ext| ext == "txt"))
-        .collect();
-    inputs.sort_by_key(|x| x.path());
+fn check_reports(reports: &[FileReport]) -> Result<()> {
+    let parts = || reports.iter().flat_map(|r| r.parts.iter());
+    let failures = parts()
+        .filter(|p| p.verdict.as_ref().is_some_and(|v| !v.is_pass()))
+        .count();
+    let errors = parts().filter(|p| p.status() == Status::Error).count();
+    let timeouts = parts().filter(|p| p.status() == Status::Timeout).count();
+    let mismatches = parts().filter(|p| p.status() == Status::Mismatch).count();
 
-    for input_file in inputs {
-        let path = input_file.path();
-        println!("Running on {}", path.display());
-        let input = std::fs::read_to_string(&path)
-            .wrap_err(format!("error reading file {}", path.display()))?;
-        run_on_file::<S>(part, &input).wrap_err("error running solution")?;
+    if failures > 0 || errors > 0 || timeouts > 0 || mismatches > 0 {
+        return Err(eyre!(
+            "{failures} answer(s) did not match the expected ones, {errors} part(s) failed, \
+             {timeouts} part(s) timed out, {mismatches} part(s) differed between variants"
+        ));
     }
 
     Ok(())
 }
 
-fn run_on_file<S: Solution>(part: Part, input: &str) -> Result<()> {
-    let mut solution = S::default();
-    let data = solution
-        .parse(input)
-        .map_err(|e| e.map_input(|x| x.to_owned()))
-        .wrap_err("error parsing input")?;
+/// Runs the days, then clears the screen and runs them again every time one
+/// of their input files changes. Errors are printed instead of ending the
+/// loop, which only stops on Ctrl-C.
+fn watch(args: &Args, registrations: &[&Registration]) -> Result<()> {
+    let paths = match &args.input {
+        Some(path) if path.as_os_str() == "-" => {
+            return Err(eyre!("--watch cannot be used with input from stdin"));
+        }
+        Some(path) => vec![path.clone()],
+        None => registrations
+            .iter()
+            .map(|x| input_directory(args, x))
+            .collect(),
+    };
+    let mut watcher = Watcher::new(paths);
 
-    let start = Instant::now();
+    loop {
+        if args.is_text() {
+            print!("{CLEAR_SCREEN}");
+        }
 
-    match part {
-        Part::One => {
-            let result = solution.run_part_1(&data);
-            let duration = start.elapsed();
-            println!("{result}");
-            println!("completed in {duration:?}");
-        }
-        Part::Two => {
-            let result = solution.run_part_2(&data);
-            let duration = start.elapsed();
-            println!("{result}");
-            println!("completed in {duration:?}");
+        let result = run_days(args, registrations).and_then(|reports| {
+            write_reports(args, &reports, true)?;
+            check_reports(&reports)
+        });
+
+        if let Err(e) = result {
+            eprintln!("Error: {e:?}");
+        }
+
+        let paths = watcher.paths().iter().map(|x| x.display().to_string());
+        eprintln!(
+            "\nWatching {} for changes, press Ctrl-C to stop",
+            paths.collect::<Vec<_>>().join(", ")
+        );
+        watcher.wait_for_change();
+    }
+}
+
+fn parse_day(value: &str) -> Result<u8, String> {
+    let day = value
+        .trim()
+        .parse::<u8>()
+        .map_err(|e| format!("invalid day '{value}': {e}"))?;
+
+    if ALL_DAYS.contains(&day) {
+        Ok(day)
+    } else {
+        Err(format!(
+            "day {day} is not in {}-{}",
+            ALL_DAYS.start(),
+            ALL_DAYS.end()
+        ))
+    }
+}
+
+/// Parses a duration with a unit of `ms`, `s` or `m`, or plain seconds.
+fn parse_duration(value: &str) -> Result<Duration, String> {
+    let value = value.trim();
+    let (number, unit) = match value.find(|x: char| x.is_ascii_alphabetic()) {
+        Some(i) => value.split_at(i),
+        None => (value, "s"),
+    };
+    let number: f64 = number
+        .trim()
+        .parse()
+        .map_err(|e| format!("invalid duration '{value}': {e}"))?;
+    let seconds = match unit {
+        "ms" => number / 1000.0,
+        "s" => number,
+        "m" => number * 60.0,
+        _ => return Err(format!("unknown unit '{unit}', expected ms, s or m")),
+    };
+
+    Duration::try_from_secs_f64(seconds).map_err(|e| format!("invalid duration '{value}': {e}"))
+}
+
+/// Parses a `--param`, optionally scoped to a day with a `DAY:` prefix.
+fn parse_param(value: &str) -> Result<ParamOverride, String> {
+    let (day, assignment) = match value.split_once(':') {
+        Some((day, assignment)) if !day.contains('=') => (Some(parse_day(day)?), assignment),
+        _ => (None, value),
+    };
+    let (key, value) = params::parse_assignment(assignment).map_err(|e| e.to_string())?;
+
+    Ok(ParamOverride { day, key, value })
+}
+
+fn parse_days(value: &str) -> Result<RangeInclusive<u8>, String> {
+    match value.split_once('-') {
+        Some((from, to)) => {
+            let (from, to) = (parse_day(from)?, parse_day(to)?);
+
+            if from <= to {
+                Ok(from..=to)
+            } else {
+                Err(format!("empty range of days '{value}'"))
+            }
         }
+        None => parse_day(value).map(|x| x..=x),
+    }
+}
+
+fn run(
+    registration: &Registration,
+    source: &InputSource,
+    args: &Args,
+    output: &mut JobOutput,
+) -> Result<FileReport> {
+    info!("Running on {source}");
+    let input = source.read()?;
+    let expected = source.expected_answers()?;
+    let mut params = source.params()?;
+    params.extend(&args.params_for(registration.day));
+
+    run_on_file(
+        registration,
+        source,
+        args,
+        &input,
+        &expected,
+        &params,
+        output,
+    )
+    .wrap_err("error running solution")
+}
+
+fn run_on_file(
+    registration: &Registration,
+    source: &InputSource,
+    args: &Args,
+    input: &str,
+    expected: &ExpectedAnswers,
+    params: &Params,
+    output: &mut JobOutput,
+) -> Result<FileReport> {
+    let _span = info_span!("run", day = registration.day, file = %source).entered();
+    let mut solution = create_solution(registration, &args.variant, params)
+        .wrap_err(format!("invalid parameters for {source}"))?;
+    let before_parse = Instant::now();
+    let (data, parse_memory) =
+        info_span!("parse").in_scope(|| memory::track(|| solution.parse(input)));
+    let parse_duration = before_parse.elapsed();
+    let data = data
+        .map_err(|e| e.with_file(source))
+        .wrap_err("error parsing input")?;
+
+    if let Some(memory) = &parse_memory {
+        info!("Parsing used {memory}");
+    }
+
+    let mut parts = vec![];
+
+    let data = data.as_ref();
+
+    let mut run_part = |part, output: &mut JobOutput| {
+        run_part(&mut *solution, part, data, source, args, expected, output)
+    };
+
+    match args.part.expect("required without a subcommand") {
+        Part::One => parts.push(run_part(1, output)),
+        Part::Two => parts.push(run_part(2, output)),
         Part::Both => {
-            let result1 = solution.run_part_1(&data);
-            let after_part_1 = Instant::now();
-            let part_1_duration = after_part_1 - start;
-            println!("Part 1:\n{result1}");
-            println!("completed in {part_1_duration:?}");
+            for part in [1, 2] {
+                parts.push(run_part(part, output));
+            }
+
+            let total = parts[0].duration + parts[1].duration;
+            info!("total elapsed: {total:?}");
+        }
+    }
+
+    if args.cross_check {
+        cross_check(registration, source, args, input, params, &mut parts)?;
+    }
+
+    let mut report = FileReport {
+        day: registration.day,
+        file: source.name(),
+        variant: registration
+            .variant_names()
+            .find(|&x| x == args.variant)
+            .expect("variant already validated"),
+        parse_duration,
+        parse_stats: None,
+        parse_memory,
+        parts,
+    };
+
+    if args.bench.is_some() {
+        run_benchmark(registration, &mut report, args, params, input, data, output);
+    }
+
+    Ok(report)
+}
+
+fn run_benchmark(
+    registration: &Registration,
+    report: &mut FileReport,
+    args: &Args,
+    params: &Params,
+    input: &str,
+    data: &dyn Any,
+    output: &mut JobOutput,
+) {
+    let _span = info_span!("bench").entered();
+    let iterations = args.bench.unwrap_or(1) as usize;
+    let warm_up = (iterations / 10).max(1);
+    let mut rows = vec![];
+    let create = |cancellation| {
+        let mut solution = create_solution(registration, &args.variant, params)
+            .expect("parameters already validated");
+        solution.set_cancellation(cancellation);
+        solution
+    };
+    let mut row = |phase: String, stats: &Result<Stats, SolutionError>| match stats {
+        Ok(stats) => rows.push(report::stats_row(phase, stats)),
+        Err(e) => {
+            warn!("benchmark of {} {phase} stopped: {e}", report.file);
+            rows.push(report::failed_row(phase, e));
+        }
+    };
+
+    let parse_stats = bench::measure(warm_up, iterations, args.timeout, create, |mut solution| {
+        solution.parse(input)
+    });
+    row("parse".to_owned(), &parse_stats);
+    report.parse_stats = parse_stats.ok();
+
+    for part in report.parts.iter_mut().filter(|p| p.result.is_ok()) {
+        let stats = bench::measure(warm_up, iterations, args.timeout, create, |mut solution| {
+            solve_part(&mut *solution, part.part, data)
+        });
+        row(format!("part {}", part.part), &stats);
+        part.stats = stats.ok();
+    }
+
+    if args.is_text() {
+        output.out(format!(
+            "benchmark over {iterations} iterations after {warm_up} warm-up run(s):"
+        ));
+        output.out(report::format_table(
+            ["phase", "min", "median", "mean", "p95", "stddev"],
+            &rows,
+        ));
+    }
+}
+
+/// Runs every variant of the day other than the selected one on the same
+/// input, and records their answers to the parts that were solved.
+fn cross_check(
+    registration: &Registration,
+    source: &InputSource,
+    args: &Args,
+    input: &str,
+    params: &Params,
+    parts: &mut [PartReport],
+) -> Result<()> {
+    for variant in registration.variant_names().filter(|&x| x != args.variant) {
+        let _span = info_span!("cross_check", variant).entered();
+        let mut solution = create_solution(registration, variant, params).wrap_err(format!(
+            "invalid parameters for {source} in variant '{variant}'"
+        ))?;
+        let data = solution
+            .parse(input)
+            .map_err(|e| e.with_file(source))
+            .wrap_err(format!("error parsing input in variant '{variant}'"))?;
 
-            let before_part_2 = Instant::now();
-            let result2 = solution.run_part_2(&data);
-            let after_part_2 = Instant::now();
-            let part_2_duration = after_part_2 - before_part_2;
-            println!("Part 2:\n{result2}");
-            println!("completed in {part_2_duration:?}");
+        for part in parts.iter_mut().filter(|p| p.result.is_ok()) {
+            let cancellation = CancellationToken::new();
+            solution.set_cancellation(cancellation.clone());
+            let timer = args.timeout.map(|x| cancellation.cancel_after(x));
+            let result =
+                solve_part(&mut *solution, part.part, data.as_ref()).map(|x| x.to_string());
+            drop(timer);
 
-            println!("total elapsed: {:?}", part_1_duration + part_2_duration);
+            match &result {
+                Ok(answer) if part.result.as_ref() == Ok(answer) => {
+                    info!("cross-check of part {} agrees", part.part);
+                }
+                Ok(answer) => warn!(
+                    "cross-check of {source} part {} with variant '{variant}': \
+                     MISMATCH, answered {}",
+                    part.part,
+                    answer.trim_end()
+                ),
+                Err(e) => warn!(
+                    "cross-check of {source} part {} with variant '{variant}': MISMATCH, {e}",
+                    part.part
+                ),
+            }
+
+            part.cross_checks.push(CrossCheck { variant, result });
         }
     }
 
     Ok(())
 }
+
+/// Creates the given variant of a solution configured with `params`, failing
+/// on unknown keys.
+fn create_solution(
+    registration: &Registration,
+    variant: &str,
+    params: &Params,
+) -> Result<Box<dyn DynSolution>, ParamError> {
+    let mut solution = registration
+        .create_variant(variant)
+        .expect("variant already validated");
+    let mut params = params.clone();
+    solution.configure(&mut params)?;
+    params.ensure_all_applied()?;
+    Ok(solution)
+}
+
+fn solve_part(
+    solution: &mut dyn DynSolution,
+    part: u8,
+    data: &dyn Any,
+) -> Result<Answer, SolutionError> {
+    match part {
+        1 => solution.run_part_1(data),
+        _ => solution.run_part_2(data),
+    }
+}
+
+fn run_part(
+    solution: &mut dyn DynSolution,
+    part: u8,
+    data: &dyn Any,
+    source: &InputSource,
+    args: &Args,
+    expected: &ExpectedAnswers,
+    output: &mut JobOutput,
+) -> PartReport {
+    let _span = info_span!("part", part).entered();
+    let cancellation = CancellationToken::new();
+    solution.set_cancellation(cancellation.clone());
+    let timer = args.timeout.map(|x| cancellation.cancel_after(x));
+
+    let start = Instant::now();
+    let (result, memory) = memory::track(|| solve_part(solution, part, data));
+    let duration = start.elapsed();
+    drop(timer);
+    let expected = match part {
+        1 => expected.part_1(),
+        _ => expected.part_2(),
+    };
+
+    let result = result.map(|x| x.to_string());
+    let verdict = match &result {
+        Ok(answer) => expected.map(|x| expected::check(x, answer)),
+        Err(SolutionError::Cancelled) => {
+            output.err(format!(
+                "{source} part {part} timed out after {duration:.2?}"
+            ));
+            None
+        }
+        Err(SolutionError::NotImplemented) => {
+            warn!("{source} part {part} is not implemented yet, skipping it");
+            None
+        }
+        Err(SolutionError::NoSuchPart) => {
+            info!("the puzzle has no part {part}, skipping it");
+            None
+        }
+        Err(e) => {
+            output.err(format!("error in {source} part {part}: {e}"));
+            None
+        }
+    };
+
+    if let (true, Ok(answer)) = (args.is_text(), &result) {
+        // Answers over several lines, such as the screen of day 10, start on
+        // the line after the label.
+        let separator = if answer.trim_end().contains('\n') {
+            "\n"
+        } else {
+            " "
+        };
+        output.out(format!(
+            "{source} part {part}:{separator}{}",
+            answer.trim_end()
+        ));
+    }
+    if let Some(verdict) = &verdict {
+        output.err(format!("{source} part {part}: {verdict}"));
+    }
+    info!("completed in {duration:?}");
+    if let Some(memory) = &memory {
+        info!("used {memory}");
+    }
+
+    PartReport {
+        part,
+        result,
+        duration,
+        stats: None,
+        memory,
+        verdict,
+        cross_checks: vec![],
+    }
+}
@@ -0,0 +1,123 @@
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+/// Named values that override the defaults of a solution, such as the row to
+/// scan or the number of rounds, which often differ between the example and
+/// the real input.
+///
+/// Values stay untyped until a solution takes them in
+/// [`Solution::configure`](crate::Solution::configure), so every key left
+/// over afterwards is not known to that solution.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Params {
+    values: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParamError {
+    message: String,
+}
+
+impl Params {
+    /// Path of the parameter sidecar of an input file, e.g. `example0.params`
+    /// for `example0.txt`.
+    pub fn sidecar_path(input_path: &Path) -> PathBuf {
+        input_path.with_extension("params")
+    }
+
+    /// Parses `key=value` lines. Empty lines and lines starting with `#` are
+    /// ignored.
+    pub fn parse(contents: &str) -> Result<Self, ParamError> {
+        let mut params = Self::default();
+
+        for (i, line) in contents.lines().enumerate() {
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (key, value) = parse_assignment(line).map_err(|e| ParamError {
+                message: format!("line {}: {}", i + 1, e.message),
+            })?;
+            params.insert(key, value);
+        }
+
+        Ok(params)
+    }
+
+    pub fn insert<K: Into<String>, V: Into<String>>(&mut self, key: K, value: V) {
+        self.values.insert(key.into(), value.into());
+    }
+
+    /// Adds all values of `other`, replacing the ones with the same key.
+    pub fn extend(&mut self, other: &Params) {
+        self.values
+            .extend(other.values.iter().map(|(k, v)| (k.clone(), v.clone())));
+    }
+
+    /// Removes `key` and parses its value into `target`, leaving `target`
+    /// untouched if the key is not present.
+    pub fn apply<T>(&mut self, key: &str, target: &mut T) -> Result<(), ParamError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        if let Some(value) = self.values.remove(key) {
+            *target = value.trim().parse().map_err(|e| ParamError {
+                message: format!("invalid value '{value}' for parameter '{key}': {e}"),
+            })?;
+        }
+
+        Ok(())
+    }
+
+    /// Fails if any values have not been taken by [`Params::apply`].
+    pub fn ensure_all_applied(&self) -> Result<(), ParamError> {
+        if self.values.is_empty() {
+            return Ok(());
+        }
+
+        let keys: Vec<_> = self.values.keys().map(String::as_str).collect();
+
+        Err(ParamError {
+            message: format!("unknown parameter(s): {}", keys.join(", ")),
+        })
+    }
+}
+
+/// Parses a single `key=value` assignment, as given on the command line.
+pub fn parse_assignment(assignment: &str) -> Result<(String, String), ParamError> {
+    let (key, value) = assignment.split_once('=').ok_or_else(|| ParamError {
+        message: format!("expected key=value, found '{assignment}'"),
+    })?;
+    let key = key.trim();
+
+    if key.is_empty() {
+        return Err(ParamError {
+            message: format!("missing parameter name in '{assignment}'"),
+        });
+    }
+
+    Ok((key.to_owned(), value.trim().to_owned()))
+}
+
+impl ParamError {
+    pub fn new<D: Display>(message: D) -> Self {
+        Self {
+            message: message.to_string(),
+        }
+    }
+}
+
+impl Display for ParamError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for ParamError {}
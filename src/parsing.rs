@@ -1,15 +1,17 @@
-use std::str::FromStr;
+use std::{fmt::Display, str::FromStr};
 
 use nom::{
     character::complete::{char, digit1, line_ending, multispace0},
     combinator::{map_res, opt, recognize},
-    error::ParseError,
+    error::{context, ErrorKind, ParseError as NomParseError, VerboseError, VerboseErrorKind},
     multi::separated_list0,
     sequence::{terminated, tuple},
-    IResult, Parser,
+    Parser,
 };
 
-pub fn line_separated<I, O, E, F>(f: F) -> impl FnMut(I) -> IResult<I, Vec<O>, E>
+pub type IResult<I, O> = nom::IResult<I, O, VerboseError<I>>;
+
+pub fn line_separated<I, O, E, F>(f: F) -> impl FnMut(I) -> nom::IResult<I, Vec<O>, E>
 where
     I: Clone
         + nom::InputLength
@@ -21,13 +23,187 @@ where
         + nom::Compare<&'static str>
         + nom::InputTakeAtPosition,
     F: Parser<I, O, E>,
-    E: ParseError<I>,
+    E: NomParseError<I>,
     <I as nom::InputIter>::Item: nom::AsChar + std::clone::Clone,
     <I as nom::InputTakeAtPosition>::Item: nom::AsChar + std::clone::Clone,
 {
     terminated(separated_list0(line_ending, f), multispace0)
 }
 
+/// Parses the whole input as lines separated by line endings.
+///
+/// Unlike `all_consuming(line_separated(f))`, which can only report that the
+/// input was not consumed, a line that fails to parse reports the error of `f`
+/// at the exact position within that line.
+pub fn all_lines<'a, O, F>(mut f: F) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>>
+where
+    F: Parser<&'a str, O, VerboseError<&'a str>>,
+{
+    move |input: &'a str| {
+        let (rest, lines) = line_separated(|x| f.parse(x))(input)?;
+
+        if rest.is_empty() {
+            return Ok((rest, lines));
+        }
+
+        let consumed = &input[..input.len() - rest.len()];
+        let is_line_start = consumed
+            .rsplit('\n')
+            .next()
+            .is_some_and(|x| x.trim().is_empty());
+
+        if is_line_start {
+            f.parse(rest)?;
+        } else {
+            line_ending(rest)?;
+        }
+
+        Err(nom::Err::Error(VerboseError::from_error_kind(
+            rest,
+            ErrorKind::Eof,
+        )))
+    }
+}
+
 pub fn integer<F: FromStr>(input: &str) -> IResult<&str, F> {
-    map_res(recognize(tuple((opt(char('-')), digit1))), str::parse)(input)
+    context(
+        "integer",
+        map_res(recognize(tuple((opt(char('-')), digit1))), str::parse),
+    )(input)
+}
+
+/// A parse failure located in the original input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    file: Option<String>,
+    line: usize,
+    column: usize,
+    line_text: String,
+    expected: String,
+    context: Option<&'static str>,
+}
+
+pub trait ParseFinish<O> {
+    /// Converts the result of a parser run on `input` into a [`ParseError`].
+    fn finish_parse(self, input: &str) -> Result<O, ParseError>;
+}
+
+impl<O> ParseFinish<O> for IResult<&str, O> {
+    fn finish_parse(self, input: &str) -> Result<O, ParseError> {
+        self.map(|x| x.1).map_err(|e| ParseError::new(input, e))
+    }
 }
+
+impl ParseError {
+    pub fn new(input: &str, error: nom::Err<VerboseError<&str>>) -> Self {
+        let errors = match error {
+            nom::Err::Incomplete(_) => {
+                vec![(&input[input.len()..], VerboseErrorKind::Nom(ErrorKind::Eof))]
+            }
+            nom::Err::Error(e) | nom::Err::Failure(e) => e.errors,
+        };
+        let (location, deepest) = errors
+            .first()
+            .cloned()
+            .unwrap_or((input, VerboseErrorKind::Nom(ErrorKind::Fail)));
+        let contexts: Vec<_> = errors
+            .iter()
+            .filter_map(|(at, kind)| match kind {
+                VerboseErrorKind::Context(ctx) => Some((*at, *ctx)),
+                _ => None,
+            })
+            .collect();
+        let is_alternative = errors
+            .get(1)
+            .is_some_and(|(_, kind)| *kind == VerboseErrorKind::Nom(ErrorKind::Alt));
+
+        // A context only describes what was expected if it starts where the
+        // parser failed; otherwise it is what the failing parser was part of.
+        let (expected, context) = match (deepest, contexts.first()) {
+            (VerboseErrorKind::Char(c), ctx) if !is_alternative => {
+                (format!("'{c}'"), ctx.map(|x| x.1))
+            }
+            (VerboseErrorKind::Nom(ErrorKind::Eof), ctx) => {
+                ("end of input".to_owned(), ctx.map(|x| x.1))
+            }
+            (_, Some((at, ctx))) if *at == location => {
+                (ctx.to_string(), contexts.get(1).map(|x| x.1))
+            }
+            (VerboseErrorKind::Nom(kind), ctx) => (describe(kind), ctx.map(|x| x.1)),
+            (VerboseErrorKind::Char(c), ctx) => (format!("'{c}'"), ctx.map(|x| x.1)),
+            (VerboseErrorKind::Context(ctx), _) => (ctx.to_owned(), None),
+        };
+
+        let offset = location.as_ptr() as usize - input.as_ptr() as usize;
+        let consumed = &input[..offset];
+        let line = consumed.matches('\n').count() + 1;
+        let line_start = consumed.rfind('\n').map_or(0, |x| x + 1);
+        let column = consumed[line_start..].chars().count() + 1;
+        let line_text = input[line_start..]
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .to_owned();
+
+        Self {
+            file: None,
+            line,
+            column,
+            line_text,
+            expected,
+            context,
+        }
+    }
+
+    pub fn with_file<D: Display>(mut self, file: D) -> Self {
+        self.file = Some(file.to_string());
+        self
+    }
+
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    pub fn column(&self) -> usize {
+        self.column
+    }
+}
+
+fn describe(kind: ErrorKind) -> String {
+    let description = match kind {
+        ErrorKind::Digit => "a digit",
+        ErrorKind::Alpha => "a letter",
+        ErrorKind::AlphaNumeric => "a letter or digit",
+        ErrorKind::Space | ErrorKind::MultiSpace => "whitespace",
+        ErrorKind::CrLf => "end of line",
+        ErrorKind::Tag => "a keyword",
+        ErrorKind::Satisfy | ErrorKind::Char => "a matching character",
+        ErrorKind::MapRes | ErrorKind::MapOpt | ErrorKind::Verify => "a valid value",
+        kind => return kind.description().to_lowercase(),
+    };
+
+    description.to_owned()
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let gutter = self.line.to_string().len();
+
+        write!(f, "expected {}", self.expected)?;
+        if let Some(context) = self.context {
+            write!(f, " in {context}")?;
+        }
+        writeln!(f)?;
+
+        write!(f, "{:gutter$}--> ", "")?;
+        if let Some(file) = &self.file {
+            write!(f, "{file}:")?;
+        }
+        writeln!(f, "{}:{}", self.line, self.column)?;
+        writeln!(f, "{:gutter$} |", "")?;
+        writeln!(f, "{} | {}", self.line, self.line_text)?;
+        write!(f, "{:gutter$} | {:>column$}", "", "^", column = self.column)
+    }
+}
+
+impl std::error::Error for ParseError {}
@@ -0,0 +1,155 @@
+use crate::*;
+use std::any::Any;
+
+/// Object-safe counterpart of [`Solution`].
+///
+/// The parsed input is passed around as `dyn Any`, so it must come from a call
+/// to [`DynSolution::parse`] on the same solution type.
+pub trait DynSolution {
+    fn configure(&mut self, params: &mut Params) -> Result<(), ParamError>;
+
+    fn set_cancellation(&mut self, token: CancellationToken);
+
+    fn parse(&mut self, input: &str) -> Result<Box<dyn Any>, ParseError>;
+
+    fn run_part_1(&mut self, data: &dyn Any) -> Result<Answer, SolutionError>;
+
+    fn run_part_2(&mut self, data: &dyn Any) -> Result<Answer, SolutionError>;
+}
+
+impl<S> DynSolution for S
+where
+    S: Solution,
+    S::Input: 'static,
+{
+    fn configure(&mut self, params: &mut Params) -> Result<(), ParamError> {
+        Solution::configure(self, params)
+    }
+
+    fn set_cancellation(&mut self, token: CancellationToken) {
+        Solution::set_cancellation(self, token)
+    }
+
+    fn parse(&mut self, input: &str) -> Result<Box<dyn Any>, ParseError> {
+        Solution::parse(self, input).map(|x| Box::new(x) as Box<dyn Any>)
+    }
+
+    fn run_part_1(&mut self, data: &dyn Any) -> Result<Answer, SolutionError> {
+        Solution::run_part_1(self, downcast_input::<S>(data)).map(Into::into)
+    }
+
+    fn run_part_2(&mut self, data: &dyn Any) -> Result<Answer, SolutionError> {
+        Solution::run_part_2(self, downcast_input::<S>(data)).map(Into::into)
+    }
+}
+
+fn downcast_input<S: Solution>(data: &dyn Any) -> &S::Input
+where
+    S::Input: 'static,
+{
+    data.downcast_ref()
+        .expect("input was not parsed by the same solution")
+}
+
+include!("registry/variants.rs");
+
+pub struct Registration {
+    pub day: u8,
+    pub name: &'static str,
+    factory: fn() -> Box<dyn DynSolution>,
+    variants: &'static [Variant],
+}
+
+/// Another implementation of a day, e.g. a different algorithm for one of the
+/// parts, which must give the same answers.
+struct Variant {
+    name: &'static str,
+    factory: fn() -> Box<dyn DynSolution>,
+}
+
+impl Registration {
+    pub fn create(&self) -> Box<dyn DynSolution> {
+        (self.factory)()
+    }
+
+    /// Creates the variant with the given name, or `None` if the day has no
+    /// such variant.
+    pub fn create_variant(&self, name: &str) -> Option<Box<dyn DynSolution>> {
+        if name == DEFAULT_VARIANT {
+            return Some(self.create());
+        }
+
+        self.variants
+            .iter()
+            .find(|x| x.name == name)
+            .map(|x| (x.factory)())
+    }
+
+    /// Names of all variants, starting with [`DEFAULT_VARIANT`].
+    pub fn variant_names(&self) -> impl Iterator<Item = &'static str> {
+        std::iter::once(DEFAULT_VARIANT).chain(self.variants.iter().map(|x| x.name))
+    }
+}
+
+fn create<S>() -> Box<dyn DynSolution>
+where
+    S: Solution + 'static,
+    S::Input: 'static,
+{
+    Box::<S>::default()
+}
+
+/// Registers every day as `day => Type, "Name";`, optionally followed by its
+/// other variants as `, "variant" => Type`.
+macro_rules! registry {
+    ($($day:literal => $solution:ty, $name:literal $(, $variant:literal => $variant_solution:ty)*;)*) => {
+        static SOLUTIONS: &[Registration] = &[
+            $(Registration {
+                day: $day,
+                name: $name,
+                factory: create::<$solution>,
+                variants: &[$(Variant {
+                    name: $variant,
+                    factory: create::<$variant_solution>,
+                },)*],
+            },)*
+        ];
+    };
+}
+
+registry! {
+    1 => Day1, "Calorie Counting";
+    2 => Day2, "Rock Paper Scissors";
+    3 => Day3, "Rucksack Reorganization";
+    4 => Day4, "Camp Cleanup";
+    5 => Day5, "Supply Stacks";
+    6 => Day6, "Tuning Trouble";
+    7 => Day7, "No Space Left On Device";
+    8 => Day8, "Treetop Tree House", "monotonic-stack" => Day8MonotonicStack;
+    9 => Day9, "Rope Bridge";
+    10 => Day10, "Cathode-Ray Tube";
+    11 => Day11, "Monkey in the Middle";
+    12 => Day12, "Hill Climbing Algorithm";
+    13 => Day13, "Distress Signal";
+    14 => Day14, "Regolith Reservoir";
+    15 => Day15, "Beacon Exclusion Zone", "geometric" => Day15Geometric;
+    16 => Day16, "Proboscidea Volcanium";
+    17 => Day17, "Pyroclastic Flow";
+    18 => Day18, "Boiling Boulders";
+    19 => Day19, "Not Enough Minerals";
+    20 => Day20, "Grove Positioning System";
+    21 => Day21, "Monkey Math";
+    22 => Day22, "Monkey Map";
+    23 => Day23, "Unstable Diffusion";
+    24 => Day24, "Blizzard Basin";
+    25 => Day25, "Full of Hot Air";
+}
+
+/// All registered solutions, ordered by day.
+pub fn all() -> &'static [Registration] {
+    SOLUTIONS
+}
+
+pub fn get(day: u8) -> Option<&'static Registration> {
+    SOLUTIONS.iter().find(|x| x.day == day)
+}
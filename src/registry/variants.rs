@@ -0,0 +1,37 @@
+// The variant names of every day, apart from the `registry!` invocation so
+// that the build script can `include!` them to generate a regression test per
+// variant. The regression tests check that they match the registry.
+
+/// Name of the variant a day is registered with, used unless another one is
+/// selected.
+pub const DEFAULT_VARIANT: &str = "default";
+
+/// Names of the variants of every registered day, starting with
+/// [`DEFAULT_VARIANT`].
+pub const VARIANTS: &[(u8, &[&str])] = &[
+    (1, &[DEFAULT_VARIANT]),
+    (2, &[DEFAULT_VARIANT]),
+    (3, &[DEFAULT_VARIANT]),
+    (4, &[DEFAULT_VARIANT]),
+    (5, &[DEFAULT_VARIANT]),
+    (6, &[DEFAULT_VARIANT]),
+    (7, &[DEFAULT_VARIANT]),
+    (8, &[DEFAULT_VARIANT, "monotonic-stack"]),
+    (9, &[DEFAULT_VARIANT]),
+    (10, &[DEFAULT_VARIANT]),
+    (11, &[DEFAULT_VARIANT]),
+    (12, &[DEFAULT_VARIANT]),
+    (13, &[DEFAULT_VARIANT]),
+    (14, &[DEFAULT_VARIANT]),
+    (15, &[DEFAULT_VARIANT, "geometric"]),
+    (16, &[DEFAULT_VARIANT]),
+    (17, &[DEFAULT_VARIANT]),
+    (18, &[DEFAULT_VARIANT]),
+    (19, &[DEFAULT_VARIANT]),
+    (20, &[DEFAULT_VARIANT]),
+    (21, &[DEFAULT_VARIANT]),
+    (22, &[DEFAULT_VARIANT]),
+    (23, &[DEFAULT_VARIANT]),
+    (24, &[DEFAULT_VARIANT]),
+    (25, &[DEFAULT_VARIANT]),
+];
@@ -0,0 +1,231 @@
+use crate::Answer;
+use std::{
+    cmp,
+    fmt::{self, Display},
+    iter::Sum,
+    ops::{Add, AddAssign, Neg},
+    str::FromStr,
+};
+
+/// A number in SNAFU, the balanced base 5 of the elves' fuel requirements.
+///
+/// Each digit is worth five times the one to its right, and goes from `=`
+/// for -2 and `-` for -1 through `0`, `1` and `2`. Numbers are added digit by
+/// digit, so sums of any size work without converting to an integer.
+///
+/// ```
+/// use advent_of_code_2022::snafu::Snafu;
+///
+/// let sum = "1=-0-2".parse::<Snafu>().unwrap() + Snafu::from(906_i64);
+/// assert_eq!(sum.to_string(), "1-111=");
+/// assert_eq!(i64::try_from(&sum), Ok(2_653));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Snafu {
+    /// Digits from -2 to 2, least significant first and without leading
+    /// zeros, so zero has no digits.
+    digits: Vec<i8>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SnafuError {
+    Empty,
+    InvalidDigit(char),
+    /// The number does not fit the integer type it is converted to.
+    Overflow,
+}
+
+const BASE: i8 = 5;
+
+impl Snafu {
+    pub fn zero() -> Self {
+        Self::default()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.digits.is_empty()
+    }
+
+    fn from_digits(mut digits: Vec<i8>) -> Self {
+        while digits.last() == Some(&0) {
+            digits.pop();
+        }
+
+        Self { digits }
+    }
+}
+
+/// Splits a digit sum from -5 to 5 into a digit and a carry.
+fn balance(sum: i8) -> (i8, i8) {
+    match sum {
+        3.. => (sum - BASE, 1),
+        ..=-3 => (sum + BASE, -1),
+        _ => (sum, 0),
+    }
+}
+
+impl FromStr for Snafu {
+    type Err = SnafuError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(SnafuError::Empty);
+        }
+
+        let digits = s
+            .chars()
+            .rev()
+            .map(|x| match x {
+                '=' => Ok(-2),
+                '-' => Ok(-1),
+                '0' => Ok(0),
+                '1' => Ok(1),
+                '2' => Ok(2),
+                _ => Err(SnafuError::InvalidDigit(x)),
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self::from_digits(digits))
+    }
+}
+
+impl Display for Snafu {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_zero() {
+            return write!(f, "0");
+        }
+
+        for digit in self.digits.iter().rev() {
+            let symbol = match digit {
+                -2 => '=',
+                -1 => '-',
+                0 => '0',
+                1 => '1',
+                _ => '2',
+            };
+            write!(f, "{symbol}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Add<&Snafu> for &Snafu {
+    type Output = Snafu;
+
+    fn add(self, other: &Snafu) -> Snafu {
+        let length = cmp::max(self.digits.len(), other.digits.len());
+        let digit = |x: &Snafu, i| x.digits.get(i).copied().unwrap_or(0);
+        let mut digits = Vec::with_capacity(length + 1);
+        let mut carry = 0;
+
+        for i in 0..length {
+            let (value, next_carry) = balance(digit(self, i) + digit(other, i) + carry);
+            digits.push(value);
+            carry = next_carry;
+        }
+
+        digits.push(carry);
+        Snafu::from_digits(digits)
+    }
+}
+
+impl Add for Snafu {
+    type Output = Snafu;
+
+    fn add(self, other: Snafu) -> Snafu {
+        &self + &other
+    }
+}
+
+impl AddAssign<&Snafu> for Snafu {
+    fn add_assign(&mut self, other: &Snafu) {
+        *self = &*self + other;
+    }
+}
+
+impl Neg for Snafu {
+    type Output = Snafu;
+
+    fn neg(self) -> Snafu {
+        Snafu {
+            digits: self.digits.into_iter().map(|x| -x).collect(),
+        }
+    }
+}
+
+impl<'a> Sum<&'a Snafu> for Snafu {
+    fn sum<I: Iterator<Item = &'a Snafu>>(iter: I) -> Self {
+        iter.fold(Snafu::zero(), |mut sum, x| {
+            sum += x;
+            sum
+        })
+    }
+}
+
+impl Sum for Snafu {
+    fn sum<I: Iterator<Item = Snafu>>(iter: I) -> Self {
+        iter.fold(Snafu::zero(), |sum, x| sum + x)
+    }
+}
+
+macro_rules! impl_integer_conversions {
+    ($($integer:ty),*) => {
+        $(
+            impl From<$integer> for Snafu {
+                fn from(mut value: $integer) -> Self {
+                    let mut digits = vec![];
+
+                    while value != 0 {
+                        let (digit, carry) = balance((value % 5) as i8);
+                        digits.push(digit);
+                        value = value / 5 + carry as $integer;
+                    }
+
+                    Self::from_digits(digits)
+                }
+            }
+
+            impl TryFrom<&Snafu> for $integer {
+                type Error = SnafuError;
+
+                fn try_from(value: &Snafu) -> Result<Self, Self::Error> {
+                    value.digits.iter().rev().try_fold(0 as $integer, |acc, &digit| {
+                        // Borrows from `acc` when the digit has the opposite
+                        // sign, so that `acc * 5` never goes past the result,
+                        // which may be right at the limit of the type.
+                        let (acc, digit) = match (acc.signum(), digit) {
+                            (1, ..=-1) => (acc - 1, digit + BASE),
+                            (-1, 1..) => (acc + 1, digit - BASE),
+                            _ => (acc, digit),
+                        };
+
+                        acc.checked_mul(5)
+                            .and_then(|x| x.checked_add(digit as $integer))
+                            .ok_or(SnafuError::Overflow)
+                    })
+                }
+            }
+        )*
+    };
+}
+
+impl_integer_conversions!(i64, i128);
+
+impl From<Snafu> for Answer {
+    fn from(value: Snafu) -> Self {
+        Answer::Text(value.to_string())
+    }
+}
+
+impl Display for SnafuError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SnafuError::Empty => write!(f, "a SNAFU number needs at least one digit"),
+            SnafuError::InvalidDigit(x) => write!(f, "'{x}' is not a SNAFU digit"),
+            SnafuError::Overflow => write!(f, "the SNAFU number is out of range"),
+        }
+    }
+}
+
+impl std::error::Error for SnafuError {}
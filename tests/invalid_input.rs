@@ -0,0 +1,45 @@
+//! Inputs that a solution cannot work with, which must end in a parse error or
+//! a [`SolutionError`] rather than a panic.
+
+use advent_of_code_2022::{solve, Day24, Error, Solution, SolutionError};
+
+fn assert_parse_error(day: u8, input: &str) {
+    match solve(day, 1, input) {
+        Err(Error::Parse(_)) => (),
+        other => panic!("expected a parse error for day {day}, got {other:?}"),
+    }
+}
+
+fn assert_invalid_input(day: u8, part: u8, input: &str) {
+    match solve(day, part, input) {
+        Err(Error::Solution(SolutionError::InvalidInput(_))) => (),
+        other => panic!("expected invalid input for day {day} part {part}, got {other:?}"),
+    }
+}
+
+#[test]
+fn day20_single_number() {
+    assert_invalid_input(20, 1, "0\n");
+    assert_invalid_input(20, 2, "0\n");
+}
+
+#[test]
+fn day24_short_bottom_wall() {
+    assert_parse_error(24, "#.###\n#...#\n###.\n");
+}
+
+#[test]
+fn day24_walls_and_outside_are_not_clear() {
+    let valley = Day24::default().parse("#.###\n#>..#\n###.#\n").unwrap();
+    let occupancy = valley.occupancy(0);
+
+    assert!(occupancy.is_clear(1, 0));
+    assert!(occupancy.is_clear(3, 2));
+    assert!(occupancy.is_clear(2, 1));
+    assert!(!occupancy.is_clear(1, 1));
+    assert!(!occupancy.is_clear(0, 0));
+    assert!(!occupancy.is_clear(0, 1));
+    assert!(!occupancy.is_clear(4, 1));
+    assert!(!occupancy.is_clear(1, 2));
+    assert!(!occupancy.is_clear(usize::MAX, usize::MAX));
+}
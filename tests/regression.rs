@@ -0,0 +1,69 @@
+//! Runs every input file that has expected answers through its solution.
+//!
+//! The tests are generated by the build script, so adding an input file with
+//! an `.expected` sidecar under `input/dayN/` adds its tests, one for each
+//! variant of the day. They are named after the day, the file, the variant
+//! unless it is the default one, and the part, e.g. `day15::example0_part2`
+//! and `day15::example0_geometric_part2`.
+//!
+//! The tests on the real puzzle inputs are ignored by default, since the
+//! slowest days take minutes even with optimizations. Run them with
+//! `cargo test -- --ignored`, or everything with
+//! `cargo test -- --include-ignored`.
+
+use advent_of_code_2022::{
+    expected::{self, ExpectedAnswers, Verdict},
+    registry, Params,
+};
+use std::path::Path;
+
+include!(concat!(env!("OUT_DIR"), "/regression_tests.rs"));
+
+fn check(day: u8, input_path: &str, variant: &str, part: u8) {
+    let input_path = Path::new(input_path);
+    let registration = registry::get(day).unwrap_or_else(|| panic!("day {day} is not registered"));
+    let input = std::fs::read_to_string(input_path).unwrap();
+    let expected = ExpectedAnswers::parse(
+        &std::fs::read_to_string(ExpectedAnswers::sidecar_path(input_path)).unwrap(),
+    )
+    .unwrap();
+    let mut params = match std::fs::read_to_string(Params::sidecar_path(input_path)) {
+        Ok(contents) => Params::parse(&contents).unwrap(),
+        Err(_) => Params::default(),
+    };
+
+    let mut solution = registration
+        .create_variant(variant)
+        .unwrap_or_else(|| panic!("day {day} has no variant '{variant}'"));
+    solution.configure(&mut params).unwrap();
+    params.ensure_all_applied().unwrap();
+    let data = solution
+        .parse(&input)
+        .unwrap_or_else(|e| panic!("{}", e.with_file(input_path.display())));
+
+    let (answer, expected) = match part {
+        1 => (solution.run_part_1(data.as_ref()), expected.part_1()),
+        _ => (solution.run_part_2(data.as_ref()), expected.part_2()),
+    };
+    let answer = answer.unwrap_or_else(|e| panic!("part {part} failed: {e}"));
+
+    if let Verdict::Fail(diff) = expected::check(expected.unwrap(), &answer.to_string()) {
+        panic!("wrong answer for part {part}:\n{diff}");
+    }
+}
+
+/// The tests above are generated from the table of variants, so it must list
+/// exactly the variants that are registered.
+#[test]
+fn variant_table_matches_registry() {
+    let registered: Vec<_> = registry::all()
+        .iter()
+        .map(|x| (x.day, x.variant_names().collect::<Vec<_>>()))
+        .collect();
+    let table: Vec<_> = registry::VARIANTS
+        .iter()
+        .map(|&(day, variants)| (day, variants.to_vec()))
+        .collect();
+
+    assert_eq!(table, registered);
+}
@@ -0,0 +1,59 @@
+use advent_of_code_2022::snafu::{Snafu, SnafuError};
+
+fn snafu(text: &str) -> Snafu {
+    text.parse().unwrap()
+}
+
+#[test]
+fn carries_over_several_digits() {
+    assert_eq!((snafu("2") + snafu("2")).to_string(), "1-");
+    assert_eq!((snafu("222") + snafu("1")).to_string(), "1===");
+    assert_eq!((snafu("===") + snafu("-")).to_string(), "-222");
+    assert_eq!((snafu("2222") + snafu("2222")).to_string(), "1000-");
+    assert!((snafu("1===") + snafu("-222")).is_zero());
+}
+
+#[test]
+fn sums_match_integer_sums() {
+    let values = [
+        -1_000_003_i64,
+        -62,
+        -3,
+        -1,
+        0,
+        1,
+        2,
+        3,
+        12,
+        63,
+        314_159,
+        1 << 40,
+    ];
+
+    for a in values {
+        for b in values {
+            let sum = Snafu::from(a) + Snafu::from(b);
+            assert_eq!(i64::try_from(&sum), Ok(a + b), "{a} + {b}");
+        }
+    }
+}
+
+#[test]
+fn round_trips_the_limits_of_i64() {
+    for value in [i64::MIN, i64::MIN + 1, -1, 0, 1, i64::MAX - 1, i64::MAX] {
+        let number = Snafu::from(value);
+        assert_eq!(i64::try_from(&number), Ok(value));
+        assert_eq!(i64::try_from(&snafu(&number.to_string())), Ok(value));
+    }
+}
+
+#[test]
+fn past_the_limits_of_i64() {
+    let above = Snafu::from(i64::MAX) + Snafu::from(1_i64);
+    let below = Snafu::from(i64::MIN) + Snafu::from(-1_i64);
+
+    assert_eq!(i64::try_from(&above), Err(SnafuError::Overflow));
+    assert_eq!(i64::try_from(&below), Err(SnafuError::Overflow));
+    assert_eq!(i128::try_from(&above), Ok(i64::MAX as i128 + 1));
+    assert_eq!(i128::try_from(&below), Ok(i64::MIN as i128 - 1));
+}